@@ -1,22 +1,116 @@
+use std::{
+    collections::BTreeSet,
+    future, io,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
 use color_eyre::Result;
 use crossterm::event::{Event, EventStream, KeyEventKind};
 use futures::{FutureExt, StreamExt};
+use log::{error, info};
 use ratatui::DefaultTerminal;
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    task,
+    time::{Instant, sleep_until},
+};
+
+use crate::{
+    event::AppEvent,
+    file_management::{
+        archive, compare, diff, directory::Directory, duplicates, ownership, permissions, sync,
+        usage, walk, watcher::DirectoryWatcher,
+    },
+    input_handling::input_handler,
+    state::diode::{
+        attribute_state::AttributeChange,
+        diode_state::{DiodeState, Selection},
+        duplicates_state::{DuplicatesJob, DuplicatesState},
+        entry_state::EntryState,
+        sync_state::{SyncJob, SyncState},
+    },
+    ui::app::draw,
+};
+
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+const LOAD_CHUNK_SIZE: usize = 512;
+const MAX_USAGE_SCANS: usize = 4;
+/// Archive progress is reported every this many entries.
+const ARCHIVE_PROGRESS_STEP: usize = 64;
+
+#[derive(Debug)]
+struct LoadTask {
+    pane: Selection,
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+struct ScanTask {
+    pane: Selection,
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
 
-use crate::{input_handling::input_handler, state::diode::diode_state::DiodeState, ui::app::draw};
+/// A job of which only the latest one runs, like a sync or a diff.
+#[derive(Debug)]
+struct Task {
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
 
 #[derive(Debug)]
 pub struct App {
     running: bool,
     event_stream: EventStream,
+    events: UnboundedReceiver<AppEvent>,
+    sender: UnboundedSender<AppEvent>,
+    loads: Vec<LoadTask>,
+    scans: Vec<ScanTask>,
+    scan_id: u64,
+    watcher: Option<DirectoryWatcher>,
+    changed_directories: BTreeSet<PathBuf>,
+    debounce_deadline: Option<Instant>,
+    background_cancelled: Arc<AtomicBool>,
+    sync_task: Option<Task>,
+    sync_id: u64,
+    diff_task: Option<Task>,
+    diff_id: u64,
+    duplicates_task: Option<Task>,
+    duplicates_id: u64,
     pub diode_state: DiodeState,
 }
 
 impl App {
     pub fn new(diode_state: DiodeState) -> Self {
+        let (sender, events) = mpsc::unbounded_channel();
+        let watcher = DirectoryWatcher::try_new(sender.clone())
+            .map_err(|e| error!("Filesystem watching is disabled: {}", e))
+            .ok();
+
         Self {
             running: false,
             event_stream: EventStream::new(),
+            events,
+            sender,
+            loads: Vec::new(),
+            scans: Vec::new(),
+            scan_id: 0,
+            watcher,
+            changed_directories: BTreeSet::new(),
+            debounce_deadline: None,
+            background_cancelled: Arc::new(AtomicBool::new(false)),
+            sync_task: None,
+            sync_id: 0,
+            diff_task: None,
+            diff_id: 0,
+            duplicates_task: None,
+            duplicates_id: 0,
             diode_state,
         }
     }
@@ -24,14 +118,43 @@ impl App {
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
         self.running = true;
         while self.running {
+            self.diode_state.record_visits();
+            self.update_loads();
+            self.update_watches();
+            self.update_usage();
+            self.update_compare();
+            self.update_diff();
+            self.update_sync();
+            self.update_duplicates();
+            self.update_archives();
+            self.update_attributes();
+            self.diode_state.left_state.update_visible();
+            self.diode_state.right_state.update_visible();
+            self.diode_state.left_state.update_details();
+            self.diode_state.right_state.update_details();
             terminal.draw(|v| draw(v, &mut self.diode_state))?;
-            self.handle_crossterm_events().await?;
+            self.handle_events().await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_events(&mut self) -> Result<()> {
+        let debounce = async {
+            match self.debounce_deadline {
+                Some(deadline) => sleep_until(deadline).await,
+                None => future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            event = self.event_stream.next().fuse() => self.handle_crossterm_event(event),
+            Some(event) = self.events.recv() => self.handle_app_event(event),
+            _ = debounce => self.apply_changes(),
         }
         Ok(())
     }
 
-    async fn handle_crossterm_events(&mut self) -> Result<()> {
-        let event = self.event_stream.next().fuse().await;
+    fn handle_crossterm_event(&mut self, event: Option<io::Result<Event>>) {
         if let Some(Ok(evt)) = event {
             match evt {
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
@@ -42,10 +165,619 @@ impl App {
                 _ => {}
             }
         }
-        Ok(())
+    }
+
+    fn handle_app_event(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::FileSystemChanged(paths) => {
+                self.changed_directories.extend(
+                    paths
+                        .iter()
+                        .filter_map(|v| v.parent())
+                        .map(|v| v.to_owned()),
+                );
+                // The deadline is not pushed back by later events, so files that
+                // are written continuously still get refreshed.
+                self.debounce_deadline
+                    .get_or_insert_with(|| Instant::now() + WATCH_DEBOUNCE);
+            }
+            AppEvent::EntriesLoaded {
+                pane,
+                id,
+                directory,
+                entries,
+            } => self
+                .diode_state
+                .on_entries_loaded(pane, id, &directory, entries),
+            AppEvent::LoadFinished {
+                pane,
+                id,
+                directory,
+                result,
+            } => {
+                self.loads.retain(|v| v.pane != pane || v.id != id);
+                self.diode_state
+                    .get_state_mut(pane)
+                    .on_load_finished(id, &directory, result);
+            }
+            AppEvent::TreeScanned {
+                pane,
+                id,
+                root,
+                result,
+            } => {
+                self.scans.retain(|v| v.id != id);
+                if let Some(analyzer) = &mut self.diode_state.get_state_mut(pane).analyzer {
+                    analyzer.on_scanned(id, &root, result);
+                }
+            }
+            AppEvent::CompareWalked { id, result } => self.diode_state.on_compared(id, result),
+            AppEvent::CompareHashed { id, pair, equal } => {
+                self.diode_state.on_hashed(id, pair, equal)
+            }
+            AppEvent::DiffLoaded { id, result } => {
+                self.diff_task.take_if(|v| v.id == id);
+                let diff = &mut self.diode_state.diff;
+                let Some(state) = diff.as_mut().filter(|v| v.id == id) else {
+                    return;
+                };
+                match result {
+                    Ok(v) => state.on_loaded(v),
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => {
+                        error!(
+                            "Failed to diff {:?} and {:?}: {}",
+                            state.left, state.right, e
+                        );
+                        *diff = None;
+                    }
+                }
+            }
+            AppEvent::ArchiveProgress { id, done, total } => {
+                self.diode_state.archives.on_progress(id, done, total)
+            }
+            AppEvent::ArchiveFinished { id, result } => {
+                let Some(job) = self.diode_state.archives.finish(id) else {
+                    return;
+                };
+                match result {
+                    Ok(()) => info!("Created archive {:?}", job.target),
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => error!("Failed to create archive {:?}: {}", job.target, e),
+                }
+                let directory = job.target.parent().map(|v| v.to_path_buf());
+                self.diode_state
+                    .left_state
+                    .sync_directories(directory.clone());
+                self.diode_state.right_state.sync_directories(directory);
+            }
+            AppEvent::DuplicatesFound { id, result } => {
+                self.duplicates_task.take_if(|v| v.id == id);
+                if let Some(duplicates) = &mut self.diode_state.duplicates {
+                    duplicates.on_scanned(id, result);
+                }
+            }
+            AppEvent::DuplicateResolved { id, path, result } => {
+                if let Some(duplicates) = &mut self.diode_state.duplicates {
+                    duplicates.on_resolved(id, &path, result);
+                }
+            }
+            AppEvent::DuplicatesResolved { id, paths } => {
+                self.duplicates_task.take_if(|v| v.id == id);
+                if let Some(duplicates) = &mut self.diode_state.duplicates {
+                    duplicates.on_finished(id);
+                }
+                let directories: BTreeSet<_> = paths
+                    .iter()
+                    .filter_map(|v| v.parent())
+                    .map(Path::to_path_buf)
+                    .collect();
+                self.diode_state
+                    .left_state
+                    .sync_directories(directories.iter().cloned());
+                self.diode_state.right_state.sync_directories(directories);
+            }
+            AppEvent::SyncPlanned { id, result } => {
+                if let Some(sync) = &mut self.diode_state.sync {
+                    sync.on_planned(id, result);
+                }
+            }
+            AppEvent::SyncProgress { id, done } => {
+                if let Some(sync) = &mut self.diode_state.sync {
+                    sync.on_progress(id, done);
+                }
+            }
+            AppEvent::SyncFinished { id, summary } => {
+                self.sync_task.take_if(|v| v.id == id);
+                if let Some(sync) = &mut self.diode_state.sync {
+                    sync.on_finished(id, summary);
+                }
+                // Both roots may have changed and the comparison is outdated.
+                self.diode_state.close_compare();
+                self.diode_state.left_state.refresh();
+                self.diode_state.right_state.refresh();
+            }
+            AppEvent::AttributeFailed { path, error } => {
+                error!("Failed to change {:?}: {}", path, error)
+            }
+            AppEvent::AttributesChanged { pane, directories } => self
+                .diode_state
+                .get_state_mut(pane)
+                .sync_directories(directories),
+            AppEvent::UsageScanned { path, result } => self.diode_state.usage.finish(path, result),
+        }
+    }
+
+    fn apply_changes(&mut self) {
+        self.debounce_deadline = None;
+        let directories = std::mem::take(&mut self.changed_directories);
+        for directory in &directories {
+            self.diode_state.usage.invalidate(directory);
+        }
+        self.diode_state
+            .left_state
+            .sync_directories(directories.iter().cloned());
+        self.diode_state.right_state.sync_directories(directories);
+    }
+
+    /// Cancels loads that are no longer needed and starts the newly requested ones.
+    fn update_loads(&mut self) {
+        for pane in [Selection::Left, Selection::Right] {
+            let state = self.diode_state.get_state_mut(pane);
+            state.prune_loads();
+
+            self.loads.retain(|v| {
+                let active = v.pane != pane || state.loading.is_active(v.id);
+                if !active {
+                    v.cancelled.store(true, Ordering::Relaxed);
+                }
+                active
+            });
+
+            for request in state.loading.take_requests() {
+                let cancelled = Arc::new(AtomicBool::new(false));
+                self.loads.push(LoadTask {
+                    pane,
+                    id: request.id,
+                    cancelled: cancelled.clone(),
+                });
+                spawn_load(
+                    pane,
+                    request.id,
+                    request.directory,
+                    cancelled,
+                    self.sender.clone(),
+                );
+            }
+        }
+    }
+
+    fn update_watches(&mut self) {
+        let Some(watcher) = &mut self.watcher else {
+            return;
+        };
+
+        let mut directories = self.diode_state.left_state.get_loaded_directories();
+        directories.extend(self.diode_state.right_state.get_loaded_directories());
+        // Scanned directories are watched too so their sizes are invalidated on change.
+        directories.extend(self.diode_state.usage.get_paths().cloned());
+        watcher.update(directories);
+    }
+
+    /// Queues the visible directories in auto mode and starts scans up to the
+    /// limit, along with the scans requested by analyzer views. Scans of
+    /// analyzers that were closed or rescanned are cancelled.
+    fn update_usage(&mut self) {
+        let diode_state = &mut self.diode_state;
+        if diode_state.usage.auto {
+            for state in [&diode_state.left_state, &diode_state.right_state] {
+                for (path, entry) in state.get_visible_window() {
+                    if let EntryState::Directory(_) = entry {
+                        diode_state.usage.request(path.clone());
+                    }
+                }
+            }
+        }
+
+        for path in diode_state.usage.take_requests(MAX_USAGE_SCANS) {
+            spawn_usage(path, self.background_cancelled.clone(), self.sender.clone());
+        }
+
+        for pane in [Selection::Left, Selection::Right] {
+            let analyzer = &mut diode_state.get_state_mut(pane).analyzer;
+            let request = analyzer.as_mut().and_then(|v| v.take_scan_request());
+            let current = analyzer.as_ref().map(|v| v.id);
+            self.scans.retain(|v| {
+                let active = v.pane != pane || (request.is_none() && current == Some(v.id));
+                if !active {
+                    v.cancelled.store(true, Ordering::Relaxed);
+                }
+                active
+            });
+
+            let (Some(analyzer), Some(root)) = (analyzer, request) else {
+                continue;
+            };
+            self.scan_id += 1;
+            analyzer.id = self.scan_id;
+            let id = analyzer.id;
+            let cancelled = Arc::new(AtomicBool::new(false));
+            self.scans.push(ScanTask {
+                pane,
+                id,
+                cancelled: cancelled.clone(),
+            });
+            let sender = self.sender.clone();
+            task::spawn_blocking(move || {
+                let result = usage::scan_tree(&root, &cancelled);
+                let _ = sender.send(AppEvent::TreeScanned {
+                    pane,
+                    id,
+                    root,
+                    result,
+                });
+            });
+        }
+    }
+
+    /// Walks both roots and compares the content of the files queued by
+    /// compare mode in the background.
+    fn update_compare(&mut self) {
+        let Some(compare) = &mut self.diode_state.compare else {
+            return;
+        };
+        if let Some((left, right)) = compare.take_walk_request() {
+            let id = compare.id;
+            let cancelled = compare.cancelled.clone();
+            let sender = self.sender.clone();
+            task::spawn_blocking(move || {
+                let result = compare::walk_trees(&left, &right, &cancelled);
+                let _ = sender.send(AppEvent::CompareWalked { id, result });
+            });
+        }
+
+        let requests = compare.take_requests();
+        if requests.is_empty() {
+            return;
+        }
+
+        let id = compare.id;
+        let cancelled = compare.cancelled.clone();
+        let sender = self.sender.clone();
+        task::spawn_blocking(move || {
+            for pair in requests {
+                let equal = compare::same_content(&pair.left, &pair.right, &cancelled);
+                match equal {
+                    Ok(equal) => {
+                        let _ = sender.send(AppEvent::CompareHashed { id, pair, equal });
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => return,
+                    Err(e) => error!("Failed to compare {:?}: {}", pair.left, e),
+                }
+            }
+        });
+    }
+
+    /// Starts the diff requested by the diff view, cancelling the previous
+    /// one when the view was closed or reopened.
+    fn update_diff(&mut self) {
+        let request = self
+            .diode_state
+            .diff
+            .as_mut()
+            .and_then(|v| v.take_request());
+        if (self.diode_state.diff.is_none() || request.is_some())
+            && let Some(task) = self.diff_task.take()
+        {
+            task.cancelled.store(true, Ordering::Relaxed);
+        }
+
+        let (Some(diff), Some((left, right))) = (&mut self.diode_state.diff, request) else {
+            return;
+        };
+        self.diff_id += 1;
+        diff.id = self.diff_id;
+        let id = diff.id;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.diff_task = Some(Task {
+            id,
+            cancelled: cancelled.clone(),
+        });
+        let sender = self.sender.clone();
+        task::spawn_blocking(move || {
+            let result = diff::diff_files(&left, &right, &cancelled);
+            let _ = sender.send(AppEvent::DiffLoaded { id, result });
+        });
+    }
+
+    /// Starts the job requested by the duplicates view, cancelling the
+    /// previous one when the view was closed or a new job was requested.
+    fn update_duplicates(&mut self) {
+        let request = self
+            .diode_state
+            .duplicates
+            .as_mut()
+            .and_then(|v| v.take_request());
+        if (self.diode_state.duplicates.is_none() || request.is_some())
+            && let Some(task) = self.duplicates_task.take()
+        {
+            task.cancelled.store(true, Ordering::Relaxed);
+        }
+
+        let (Some(duplicates), Some(request)) = (&mut self.diode_state.duplicates, request) else {
+            return;
+        };
+        self.duplicates_id += 1;
+        duplicates.id = self.duplicates_id;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.duplicates_task = Some(Task {
+            id: duplicates.id,
+            cancelled: cancelled.clone(),
+        });
+        spawn_duplicates(duplicates, request, cancelled, self.sender.clone());
+    }
+
+    fn update_archives(&mut self) {
+        for request in self.diode_state.archives.take_requests() {
+            let cancelled = self.background_cancelled.clone();
+            let sender = self.sender.clone();
+            task::spawn_blocking(move || {
+                let id = request.id;
+                let result = archive::create(
+                    request.format,
+                    &request.target,
+                    &request.root,
+                    &request.entries,
+                    &cancelled,
+                    &mut |done, total| {
+                        if done % ARCHIVE_PROGRESS_STEP == 0 || done == total {
+                            let _ = sender.send(AppEvent::ArchiveProgress { id, done, total });
+                        }
+                    },
+                );
+                let _ = sender.send(AppEvent::ArchiveFinished { id, result });
+            });
+        }
+    }
+
+    /// Applies mode and owner changes in the background, reporting failures per entry.
+    fn update_attributes(&mut self) {
+        for request in self.diode_state.attributes.take_requests() {
+            let cancelled = self.background_cancelled.clone();
+            let sender = self.sender.clone();
+            task::spawn_blocking(move || {
+                for target in &request.targets {
+                    change_attributes(target, &request.change, &cancelled, &sender);
+                }
+                let directories = request
+                    .targets
+                    .iter()
+                    .filter_map(|v| v.parent())
+                    .map(Path::to_path_buf)
+                    .chain(request.targets.iter().cloned())
+                    .collect();
+                let _ = sender.send(AppEvent::AttributesChanged {
+                    pane: request.pane,
+                    directories,
+                });
+            });
+        }
+    }
+
+    /// Starts the job requested by the sync dialog, cancelling the previous
+    /// one when the dialog was closed or asked for something else.
+    fn update_sync(&mut self) {
+        let request = self
+            .diode_state
+            .sync
+            .as_mut()
+            .and_then(|v| v.take_request());
+        if (self.diode_state.sync.is_none() || request.is_some())
+            && let Some(task) = self.sync_task.take()
+        {
+            task.cancelled.store(true, Ordering::Relaxed);
+        }
+
+        let (Some(sync), Some(request)) = (&mut self.diode_state.sync, request) else {
+            return;
+        };
+        self.sync_id += 1;
+        sync.id = self.sync_id;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.sync_task = Some(Task {
+            id: sync.id,
+            cancelled: cancelled.clone(),
+        });
+        spawn_sync(sync, request, cancelled, self.sender.clone());
     }
 
     pub fn quit(&mut self) {
         self.running = false;
+        // Blocking tasks keep the runtime alive, so running jobs have to stop.
+        self.background_cancelled.store(true, Ordering::Relaxed);
+        let tasks = [&self.sync_task, &self.diff_task, &self.duplicates_task];
+        for task in tasks.into_iter().flatten() {
+            task.cancelled.store(true, Ordering::Relaxed);
+        }
+        for task in &self.scans {
+            task.cancelled.store(true, Ordering::Relaxed);
+        }
+        if let Some(compare) = &self.diode_state.compare {
+            compare.cancel();
+        }
+    }
+}
+
+fn spawn_load(
+    pane: Selection,
+    id: u64,
+    directory: PathBuf,
+    cancelled: Arc<AtomicBool>,
+    sender: UnboundedSender<AppEvent>,
+) {
+    task::spawn_blocking(move || {
+        let result = Directory::load_entries(&directory, LOAD_CHUNK_SIZE, &cancelled, |entries| {
+            let _ = sender.send(AppEvent::EntriesLoaded {
+                pane,
+                id,
+                directory: directory.clone(),
+                entries,
+            });
+        });
+        let _ = sender.send(AppEvent::LoadFinished {
+            pane,
+            id,
+            directory,
+            result,
+        });
+    });
+}
+
+fn spawn_usage(path: PathBuf, cancelled: Arc<AtomicBool>, sender: UnboundedSender<AppEvent>) {
+    task::spawn_blocking(move || {
+        let result = usage::scan(&path, &cancelled);
+        let _ = sender.send(AppEvent::UsageScanned { path, result });
+    });
+}
+
+fn spawn_sync(
+    sync: &SyncState,
+    request: SyncJob,
+    cancelled: Arc<AtomicBool>,
+    sender: UnboundedSender<AppEvent>,
+) {
+    let id = sync.id;
+    match request {
+        SyncJob::Plan => {
+            let (source, destination, mode) =
+                (sync.source.clone(), sync.destination.clone(), sync.mode);
+            task::spawn_blocking(move || {
+                let result = sync::plan(&source, &destination, mode, &cancelled);
+                let _ = sender.send(AppEvent::SyncPlanned { id, result });
+            });
+        }
+        SyncJob::Run => {
+            let actions = sync.actions.clone();
+            task::spawn_blocking(move || {
+                let mut summary = sync::SyncSummary::default();
+                for (index, action) in actions.iter().enumerate() {
+                    if cancelled.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let result = action.execute();
+                    if let Err(e) = &result {
+                        error!("Failed to {} {:?}: {}", action.label(), action.target(), e);
+                    }
+                    summary.record(action, &result);
+                    let _ = sender.send(AppEvent::SyncProgress {
+                        id,
+                        done: index + 1,
+                    });
+                }
+                let _ = sender.send(AppEvent::SyncFinished { id, summary });
+            });
+        }
+    }
+}
+
+fn spawn_duplicates(
+    duplicates: &DuplicatesState,
+    request: DuplicatesJob,
+    cancelled: Arc<AtomicBool>,
+    sender: UnboundedSender<AppEvent>,
+) {
+    let id = duplicates.id;
+    match request {
+        DuplicatesJob::Scan => {
+            let roots = duplicates.get_roots();
+            task::spawn_blocking(move || {
+                let result = duplicates::find(&roots, &cancelled);
+                let _ = sender.send(AppEvent::DuplicatesFound { id, result });
+            });
+        }
+        DuplicatesJob::Resolve(action) => {
+            let marked = duplicates.get_marked_with_original();
+            task::spawn_blocking(move || {
+                let mut paths = Vec::new();
+                for (path, original) in marked {
+                    if cancelled.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let result =
+                        duplicates::resolve(&path, original.as_deref(), action, &cancelled);
+                    paths.push(path.clone());
+                    let _ = sender.send(AppEvent::DuplicateResolved { id, path, result });
+                }
+                let _ = sender.send(AppEvent::DuplicatesResolved { id, paths });
+            });
+        }
+    }
+}
+
+fn change_attributes(
+    target: &Path,
+    change: &AttributeChange,
+    cancelled: &AtomicBool,
+    sender: &UnboundedSender<AppEvent>,
+) {
+    let report = |path: &Path, error: io::Error| {
+        let _ = sender.send(AppEvent::AttributeFailed {
+            path: path.to_path_buf(),
+            error,
+        });
+    };
+    match change {
+        AttributeChange::Mode {
+            change,
+            recursion: None,
+        } => {
+            if let Err(e) = permissions::change_mode(target, change) {
+                report(target, e);
+            }
+        }
+        AttributeChange::Mode {
+            change,
+            recursion: Some(recursion),
+        } => walk::walk(
+            target,
+            &mut |path, metadata| {
+                // Links found while recursing are skipped like chmod -R does,
+                // changing them would change whatever they point to.
+                if cancelled.load(Ordering::Relaxed)
+                    || metadata.is_symlink()
+                    || !recursion.includes(metadata.is_dir())
+                {
+                    return;
+                }
+                if let Err(e) = permissions::change_mode(path, change) {
+                    report(path, e);
+                }
+            },
+            &mut |path, e| report(path, e),
+        ),
+        AttributeChange::Owner {
+            change,
+            recursive: false,
+        } => {
+            if let Err(e) = ownership::change_owner(target, *change, true) {
+                report(target, e);
+            }
+        }
+        // Links found while recursing are changed themselves, not what they point to.
+        AttributeChange::Owner {
+            change,
+            recursive: true,
+        } => walk::walk(
+            target,
+            &mut |path, _| {
+                if cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+                if let Err(e) = ownership::change_owner(path, *change, false) {
+                    report(path, e);
+                }
+            },
+            &mut |path, e| report(path, e),
+        ),
     }
 }
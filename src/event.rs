@@ -0,0 +1,95 @@
+use std::{io, path::PathBuf};
+
+use crate::{
+    file_management::{
+        compare::TreePair,
+        diff::FileDiff,
+        duplicates::DuplicateGroup,
+        entry::Entry,
+        sync::{SyncAction, SyncSummary},
+        usage::{Usage, UsageNode},
+    },
+    state::diode::{compare_state::HashPair, diode_state::Selection},
+};
+
+#[derive(Debug)]
+pub enum AppEvent {
+    FileSystemChanged(Vec<PathBuf>),
+    EntriesLoaded {
+        pane: Selection,
+        id: u64,
+        directory: PathBuf,
+        entries: Vec<Entry>,
+    },
+    LoadFinished {
+        pane: Selection,
+        id: u64,
+        directory: PathBuf,
+        result: io::Result<()>,
+    },
+    TreeScanned {
+        pane: Selection,
+        id: u64,
+        root: PathBuf,
+        result: io::Result<UsageNode>,
+    },
+    CompareWalked {
+        id: u64,
+        result: io::Result<Vec<TreePair>>,
+    },
+    CompareHashed {
+        id: u64,
+        pair: HashPair,
+        equal: bool,
+    },
+    DiffLoaded {
+        id: u64,
+        result: io::Result<FileDiff>,
+    },
+    ArchiveProgress {
+        id: u64,
+        done: usize,
+        total: usize,
+    },
+    ArchiveFinished {
+        id: u64,
+        result: io::Result<()>,
+    },
+    DuplicatesFound {
+        id: u64,
+        result: io::Result<Vec<DuplicateGroup>>,
+    },
+    DuplicateResolved {
+        id: u64,
+        path: PathBuf,
+        result: io::Result<()>,
+    },
+    DuplicatesResolved {
+        id: u64,
+        paths: Vec<PathBuf>,
+    },
+    SyncPlanned {
+        id: u64,
+        result: io::Result<Vec<SyncAction>>,
+    },
+    SyncProgress {
+        id: u64,
+        done: usize,
+    },
+    SyncFinished {
+        id: u64,
+        summary: SyncSummary,
+    },
+    AttributeFailed {
+        path: PathBuf,
+        error: io::Error,
+    },
+    AttributesChanged {
+        pane: Selection,
+        directories: Vec<PathBuf>,
+    },
+    UsageScanned {
+        path: PathBuf,
+        result: io::Result<Usage>,
+    },
+}
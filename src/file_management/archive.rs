@@ -0,0 +1,179 @@
+use std::{
+    fs::{self, File, Metadata},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use flate2::{Compression, write::GzEncoder};
+use log::warn;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::file_management::{permissions, walk};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveFormat {
+    Tar,
+    TarGz,
+    TarZst,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Picks the format from the extension of the archive name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        [
+            (".tar.gz", ArchiveFormat::TarGz),
+            (".tgz", ArchiveFormat::TarGz),
+            (".tar.zst", ArchiveFormat::TarZst),
+            (".tzst", ArchiveFormat::TarZst),
+            (".tar", ArchiveFormat::Tar),
+            (".zip", ArchiveFormat::Zip),
+        ]
+        .into_iter()
+        .find(|(extension, _)| name.ends_with(extension))
+        .map(|(_, format)| format)
+    }
+}
+
+struct ArchiveEntry {
+    path: PathBuf,
+    /// Relative to the root the archive is created from.
+    name: PathBuf,
+    metadata: Metadata,
+}
+
+/// Archives `entries` and everything below them into `target`, naming them
+/// by their path relative to `root`. Links are stored as links.
+/// `on_progress` gets the number of entries written and the total.
+pub fn create(
+    format: ArchiveFormat,
+    target: &Path,
+    root: &Path,
+    entries: &[PathBuf],
+    cancelled: &AtomicBool,
+    on_progress: &mut impl FnMut(usize, usize),
+) -> io::Result<()> {
+    let entries = collect_entries(target, root, entries)?;
+    let file = BufWriter::new(File::create_new(target)?);
+    let total = entries.len();
+    let mut on_entry = |index: usize| {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(io::Error::from(io::ErrorKind::Interrupted));
+        }
+        on_progress(index, total);
+        Ok(())
+    };
+
+    let result = match format {
+        ArchiveFormat::Tar => write_tar(file, &entries, &mut on_entry).and_then(finish_buffer),
+        ArchiveFormat::TarGz => {
+            let encoder = GzEncoder::new(file, Compression::default());
+            write_tar(encoder, &entries, &mut on_entry)
+                .and_then(|v| v.finish())
+                .and_then(finish_buffer)
+        }
+        ArchiveFormat::TarZst => zstd::Encoder::new(file, 0)
+            .and_then(|v| write_tar(v, &entries, &mut on_entry))
+            .and_then(|v| v.finish())
+            .and_then(finish_buffer),
+        ArchiveFormat::Zip => write_zip(file, &entries, &mut on_entry).and_then(finish_buffer),
+    };
+    on_progress(total, total);
+
+    // Half written archives are of no use.
+    if result.is_err()
+        && let Err(e) = fs::remove_file(target)
+    {
+        warn!("Failed to remove incomplete archive {:?}: {}", target, e);
+    }
+    result
+}
+
+fn collect_entries(
+    target: &Path,
+    root: &Path,
+    entries: &[PathBuf],
+) -> io::Result<Vec<ArchiveEntry>> {
+    let mut entries: Vec<&PathBuf> = entries.iter().collect();
+    entries.sort();
+    let mut collected = Vec::new();
+    let mut roots: Vec<&PathBuf> = Vec::new();
+    for entry in entries {
+        // Marked entries inside a marked directory are archived with it already.
+        if roots.iter().any(|v| entry.starts_with(v)) {
+            continue;
+        }
+        roots.push(entry);
+
+        let mut error = None;
+        walk::walk(
+            entry,
+            &mut |path, metadata| {
+                // The archive may be written into one of the archived directories.
+                if path == target {
+                    return;
+                }
+                let Ok(name) = path.strip_prefix(root) else {
+                    return;
+                };
+                collected.push(ArchiveEntry {
+                    path: path.to_path_buf(),
+                    name: name.to_path_buf(),
+                    metadata: metadata.clone(),
+                });
+            },
+            &mut |path, e| {
+                error.get_or_insert_with(|| io::Error::new(e.kind(), format!("{:?}: {}", path, e)));
+            },
+        );
+        if let Some(e) = error {
+            return Err(e);
+        }
+    }
+    Ok(collected)
+}
+
+fn write_tar<W: Write>(
+    writer: W,
+    entries: &[ArchiveEntry],
+    before_entry: &mut impl FnMut(usize) -> io::Result<()>,
+) -> io::Result<W> {
+    let mut builder = tar::Builder::new(writer);
+    builder.follow_symlinks(false);
+    for (index, entry) in entries.iter().enumerate() {
+        before_entry(index)?;
+        builder.append_path_with_name(&entry.path, &entry.name)?;
+    }
+    builder.into_inner()
+}
+
+fn write_zip<W: Write + io::Seek>(
+    writer: W,
+    entries: &[ArchiveEntry],
+    before_entry: &mut impl FnMut(usize) -> io::Result<()>,
+) -> io::Result<W> {
+    let mut zip = ZipWriter::new(writer);
+    for (index, entry) in entries.iter().enumerate() {
+        before_entry(index)?;
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .unix_permissions(permissions::get_mode(&entry.metadata))
+            .large_file(entry.metadata.len() > u32::MAX as u64);
+
+        if entry.metadata.is_symlink() {
+            zip.add_symlink_from_path(&entry.name, fs::read_link(&entry.path)?, options)?;
+        } else if entry.metadata.is_dir() {
+            zip.add_directory_from_path(&entry.name, options)?;
+        } else {
+            zip.start_file_from_path(&entry.name, options)?;
+            io::copy(&mut File::open(&entry.path)?, &mut zip)?;
+        }
+    }
+    Ok(zip.finish()?)
+}
+
+fn finish_buffer(writer: BufWriter<File>) -> io::Result<()> {
+    writer.into_inner().map_err(|e| e.into_error())?.sync_all()
+}
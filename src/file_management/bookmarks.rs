@@ -0,0 +1,113 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Reads one bookmark per line as `name<TAB>path`, with backslashes, tabs and
+/// line breaks in either field escaped.
+pub fn load(file: &Path) -> io::Result<BTreeMap<String, PathBuf>> {
+    let content = match fs::read_to_string(file) {
+        Ok(v) => v,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e),
+    };
+
+    Ok(content
+        .lines()
+        .filter_map(|v| v.split_once('\t'))
+        .map(|(name, path)| (unescape(name), PathBuf::from(unescape(path))))
+        .collect())
+}
+
+/// Paths that are not valid UTF-8 cannot be stored and fail the save.
+pub fn save(file: &Path, bookmarks: &BTreeMap<String, PathBuf>) -> io::Result<()> {
+    let mut content = String::new();
+    for (name, path) in bookmarks {
+        let path = path.to_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{:?} is not valid UTF-8", path),
+            )
+        })?;
+        content.push_str(&format!("{}\t{}\n", escape(name), escape(path)));
+    }
+    fs::write(file, content)
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('\\') => unescaped.push('\\'),
+            // Kept as is, so Windows paths saved before escaping still load.
+            Some(c) => {
+                unescaped.push('\\');
+                unescaped.push(c);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn special_characters_round_trip() {
+        for value in [
+            "plain",
+            "a\tb",
+            "line\nbreak\r",
+            "back\\slash",
+            "\\t",
+            "end\\",
+        ] {
+            assert_eq!(unescape(&escape(value)), value);
+            assert!(!escape(value).contains(['\t', '\n', '\r']));
+        }
+    }
+
+    #[test]
+    fn unknown_escapes_are_kept() {
+        assert_eq!(unescape("C:\\Users\\me"), "C:\\Users\\me");
+    }
+
+    #[test]
+    fn bookmarks_round_trip_through_the_file() {
+        let file = std::env::temp_dir().join(format!("diode-bookmarks-{}", std::process::id()));
+        let bookmarks = BTreeMap::from([
+            ("tab\tname".to_string(), PathBuf::from("/some\tpath")),
+            ("line\nname".to_string(), PathBuf::from("/some\\path\n")),
+        ]);
+
+        save(&file, &bookmarks).unwrap();
+        let loaded = load(&file);
+        let _ = fs::remove_file(&file);
+        assert_eq!(loaded.unwrap(), bookmarks);
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    fs::{self, Metadata},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::UNIX_EPOCH,
+};
+
+use log::warn;
+
+use crate::file_management::sync;
+
+/// How an entry relates to its counterpart, seen from the left pane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    OnlyLeft,
+    OnlyRight,
+    Newer,
+    Older,
+    Identical,
+}
+
+impl Comparison {
+    /// The same relation seen from the right pane.
+    pub fn flip(self) -> Self {
+        match self {
+            Comparison::Newer => Comparison::Older,
+            Comparison::Older => Comparison::Newer,
+            v => v,
+        }
+    }
+
+    pub fn is_difference(self) -> bool {
+        self != Comparison::Identical
+    }
+}
+
+/// Entries match when size and modification time agree. Modification times
+/// are compared in whole seconds, as not every filesystem stores more.
+pub fn compare(left: &Metadata, right: &Metadata) -> Comparison {
+    match left.len() == right.len() && modified(left) == modified(right) {
+        true => Comparison::Identical,
+        false => compare_modified(left, right),
+    }
+}
+
+/// Orders differing entries by age, counting ties as newer on the left.
+pub fn compare_modified(left: &Metadata, right: &Metadata) -> Comparison {
+    match modified(left) < modified(right) {
+        true => Comparison::Older,
+        false => Comparison::Newer,
+    }
+}
+
+fn modified(metadata: &Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|v| v.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |v| v.as_secs())
+}
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Compares the content of two files byte by byte, stopping at the first
+/// difference.
+pub fn same_content(left: &Path, right: &Path, cancelled: &AtomicBool) -> io::Result<bool> {
+    let (mut left, mut right) = (fs::File::open(left)?, fs::File::open(right)?);
+    if left.metadata()?.len() != right.metadata()?.len() {
+        return Ok(false);
+    }
+
+    let (mut left_buffer, mut right_buffer) = (vec![0; BUFFER_SIZE], vec![0; BUFFER_SIZE]);
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(io::Error::from(io::ErrorKind::Interrupted));
+        }
+        let read = read_full(&mut left, &mut left_buffer)?;
+        if read != read_full(&mut right, &mut right_buffer)?
+            || left_buffer[..read] != right_buffer[..read]
+        {
+            return Ok(false);
+        }
+        if read == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// Reads until `buffer` is full or the end of the file is reached.
+fn read_full(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..])? {
+            0 => break,
+            read => filled += read,
+        }
+    }
+    Ok(filled)
+}
+
+/// An entry below one of the compared roots along with its counterpart
+/// below the other root, if there is one.
+#[derive(Debug)]
+pub struct TreePair {
+    pub left: Option<(PathBuf, Metadata)>,
+    pub right: Option<(PathBuf, Metadata)>,
+}
+
+/// Walks both roots without following links and pairs their entries by the
+/// path relative to the roots. Unreadable subdirectories are skipped.
+pub fn walk_trees(left: &Path, right: &Path, cancelled: &AtomicBool) -> io::Result<Vec<TreePair>> {
+    let mut pairs = Vec::new();
+    walk_directories(Some(left), Some(right), true, cancelled, &mut pairs)?;
+    Ok(pairs)
+}
+
+fn walk_directories(
+    left: Option<&Path>,
+    right: Option<&Path>,
+    is_root: bool,
+    cancelled: &AtomicBool,
+    pairs: &mut Vec<TreePair>,
+) -> io::Result<()> {
+    if cancelled.load(Ordering::Relaxed) {
+        return Err(io::ErrorKind::Interrupted.into());
+    }
+
+    let read = |directory: Option<&Path>| match directory.map(sync::read_entries) {
+        None => Ok(Default::default()),
+        Some(Err(e)) if !is_root => {
+            warn!("Failed to read {:?}: {}", directory, e);
+            Ok(Default::default())
+        }
+        Some(result) => result,
+    };
+    let mut left_entries = read(left)?;
+    let mut right_entries = read(right)?;
+
+    let mut names: Vec<_> = left_entries
+        .keys()
+        .chain(right_entries.keys())
+        .cloned()
+        .collect();
+    names.sort();
+    names.dedup();
+    for name in names {
+        let pair = TreePair {
+            left: left
+                .zip(left_entries.remove(&name))
+                .map(|(d, m)| (d.join(&name), m)),
+            right: right
+                .zip(right_entries.remove(&name))
+                .map(|(d, m)| (d.join(&name), m)),
+        };
+        let directory = |side: &Option<(PathBuf, Metadata)>| {
+            side.as_ref()
+                .filter(|(_, m)| m.is_dir())
+                .map(|(p, _)| p.clone())
+        };
+        let (left_directory, right_directory) = (directory(&pair.left), directory(&pair.right));
+        pairs.push(pair);
+        if left_directory.is_some() || right_directory.is_some() {
+            walk_directories(
+                left_directory.as_deref(),
+                right_directory.as_deref(),
+                false,
+                cancelled,
+                pairs,
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use std::{fs, io, path::PathBuf};
+
+pub fn data_file(name: &str) -> io::Result<PathBuf> {
+    let directory = dirs::data_dir()
+        .ok_or_else(|| io::Error::other("No data directory available"))?
+        .join("diode");
+    fs::create_dir_all(&directory)?;
+    Ok(directory.join(name))
+}
@@ -0,0 +1,137 @@
+use std::{fs, io, path::Path, sync::atomic::AtomicBool, time::Duration};
+
+use similar::{ChangeTag, TextDiff};
+
+use crate::file_management::compare;
+
+/// Larger files are only compared by content, not line by line.
+const MAX_TEXT_SIZE: u64 = 16 * 1024 * 1024;
+/// How far into a file to look for a NUL byte to tell binary files apart.
+const BINARY_PROBE: usize = 8000;
+const CONTEXT_LINES: usize = 3;
+/// Diffs that take longer are approximated instead of keeping the view waiting.
+const DIFF_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineKind {
+    Context,
+    Removed,
+    Added,
+}
+
+#[derive(Debug)]
+pub struct DiffLine {
+    pub kind: LineKind,
+    /// Zero based line numbers in the left and right file.
+    pub left: Option<usize>,
+    pub right: Option<usize>,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct Hunk {
+    pub lines: Vec<DiffLine>,
+}
+
+impl Hunk {
+    /// The unified diff header, `@@ -start,count +start,count @@`.
+    pub fn header(&self) -> String {
+        let range = |side: fn(&DiffLine) -> Option<usize>| {
+            let start = self.lines.iter().find_map(side).map_or(0, |v| v + 1);
+            let count = self.lines.iter().filter(|v| side(v).is_some()).count();
+            format!("{},{}", start, count)
+        };
+        format!("@@ -{} +{} @@", range(|v| v.left), range(|v| v.right))
+    }
+
+    /// Pairs removed lines with the added lines replacing them, for showing
+    /// both files next to each other.
+    pub fn pair_lines(&self) -> Vec<(Option<&DiffLine>, Option<&DiffLine>)> {
+        let mut rows = Vec::new();
+        let (mut removed, mut added) = (Vec::new(), Vec::new());
+        for line in &self.lines {
+            match line.kind {
+                LineKind::Removed => {
+                    if !added.is_empty() {
+                        pair(&mut rows, &mut removed, &mut added);
+                    }
+                    removed.push(line);
+                }
+                LineKind::Added => added.push(line),
+                LineKind::Context => {
+                    pair(&mut rows, &mut removed, &mut added);
+                    rows.push((Some(line), Some(line)));
+                }
+            }
+        }
+        pair(&mut rows, &mut removed, &mut added);
+        rows
+    }
+}
+
+fn pair<'a>(
+    rows: &mut Vec<(Option<&'a DiffLine>, Option<&'a DiffLine>)>,
+    removed: &mut Vec<&'a DiffLine>,
+    added: &mut Vec<&'a DiffLine>,
+) {
+    for index in 0..removed.len().max(added.len()) {
+        rows.push((removed.get(index).copied(), added.get(index).copied()));
+    }
+    removed.clear();
+    added.clear();
+}
+
+#[derive(Debug)]
+pub enum FileDiff {
+    /// Empty when the files are equal.
+    Text(Vec<Hunk>),
+    /// Binary or too large files, which are only compared as a whole.
+    Binary { differs: bool },
+}
+
+pub fn diff_files(left: &Path, right: &Path, cancelled: &AtomicBool) -> io::Result<FileDiff> {
+    if fs::metadata(left)?.len() > MAX_TEXT_SIZE || fs::metadata(right)?.len() > MAX_TEXT_SIZE {
+        return Ok(FileDiff::Binary {
+            differs: !compare::same_content(left, right, cancelled)?,
+        });
+    }
+
+    let (left_content, right_content) = (fs::read(left)?, fs::read(right)?);
+    if is_binary(&left_content) || is_binary(&right_content) {
+        return Ok(FileDiff::Binary {
+            differs: left_content != right_content,
+        });
+    }
+
+    let left_text = String::from_utf8_lossy(&left_content);
+    let right_text = String::from_utf8_lossy(&right_content);
+    let diff = TextDiff::configure()
+        .timeout(DIFF_TIMEOUT)
+        .diff_lines(left_text.as_ref(), right_text.as_ref());
+
+    let hunks = diff
+        .grouped_ops(CONTEXT_LINES)
+        .iter()
+        .map(|group| Hunk {
+            lines: group
+                .iter()
+                .flat_map(|op| diff.iter_changes(op))
+                .map(|change| DiffLine {
+                    kind: match change.tag() {
+                        ChangeTag::Equal => LineKind::Context,
+                        ChangeTag::Delete => LineKind::Removed,
+                        ChangeTag::Insert => LineKind::Added,
+                    },
+                    left: change.old_index(),
+                    right: change.new_index(),
+                    text: change.value().trim_end_matches(['\n', '\r']).to_owned(),
+                })
+                .collect(),
+        })
+        .collect();
+    Ok(FileDiff::Text(hunks))
+}
+
+fn is_binary(content: &[u8]) -> bool {
+    content[..content.len().min(BINARY_PROBE)].contains(&0)
+}
@@ -2,32 +2,58 @@ use std::{
     ffi::OsString,
     fs::{self, Metadata},
     io::{self},
-    path::PathBuf,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
 };
 
 use log::error;
 
-use crate::file_management::entry::Entry;
+use crate::file_management::{entry::Entry, link::Link};
 
 #[derive(Debug, Clone)]
 pub struct Directory {
     pub name: OsString,
     pub path: PathBuf,
-    #[allow(dead_code)]
     pub metadata: Metadata,
+    pub link: Option<Link>,
 }
 
 impl Directory {
-    pub fn load_entries(&self) -> io::Result<Vec<Entry>> {
-        Ok(fs::read_dir(&self.path)?
-            .filter_map(|e| {
-                let entry = e.ok()?;
-                let path = entry.path();
-                Entry::try_from(entry)
-                    .map_err(|err| error!("Skipping {:?}: {}", path, err))
-                    .ok()
-            })
-            .collect())
+    /// Reads the entries of `path` and hands them to `on_chunk` in batches of
+    /// `chunk_size`, stopping early once `cancelled` is set.
+    pub fn load_entries(
+        path: &Path,
+        chunk_size: usize,
+        cancelled: &AtomicBool,
+        mut on_chunk: impl FnMut(Vec<Entry>),
+    ) -> io::Result<()> {
+        let mut chunk = Vec::with_capacity(chunk_size);
+        for e in fs::read_dir(path)? {
+            if cancelled.load(Ordering::Relaxed) {
+                return Err(io::Error::from(io::ErrorKind::Interrupted));
+            }
+
+            let Ok(entry) = e else {
+                continue;
+            };
+            let path = entry.path();
+            match Entry::try_from(entry) {
+                Ok(v) => chunk.push(v),
+                Err(err) => error!("Skipping {:?}: {}", path, err),
+            }
+
+            if chunk.len() == chunk_size {
+                on_chunk(std::mem::replace(
+                    &mut chunk,
+                    Vec::with_capacity(chunk_size),
+                ));
+            }
+        }
+
+        if !chunk.is_empty() {
+            on_chunk(chunk);
+        }
+        Ok(())
     }
 
     pub fn get_parent_directory(&self) -> io::Result<Directory> {
@@ -47,7 +73,8 @@ impl TryFrom<PathBuf> for Directory {
         Ok(Self {
             name: path.file_name().unwrap_or_default().to_owned(),
             path: path.to_path_buf(),
-            metadata: fs::metadata(path)?,
+            metadata: fs::metadata(&path)?,
+            link: Link::read(&path)?,
         })
     }
 }
@@ -0,0 +1,189 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    fs::{self, Metadata},
+    io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::warn;
+
+use crate::file_management::{compare, entry, hash, walk};
+
+/// Files of the same size are first told apart by hashing only this much.
+const PARTIAL_HASH_SIZE: u64 = 16 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct DuplicateFile {
+    pub path: PathBuf,
+    pub modified: SystemTime,
+}
+
+/// Files with the same content, sorted by path.
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub size: u64,
+    pub files: Vec<DuplicateFile>,
+}
+
+impl DuplicateGroup {
+    /// Space taken by all copies but one.
+    pub fn wasted(&self) -> u64 {
+        self.size * (self.files.len() as u64 - 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeepRule {
+    Oldest,
+    ShortestPath,
+    /// The oldest file in the left pane, or the oldest one if there is none.
+    LeftPane,
+}
+
+/// What happens to a marked duplicate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicateAction {
+    Delete,
+    /// Replace it with a hard link to the kept copy.
+    Link,
+}
+
+impl DuplicateAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            DuplicateAction::Delete => "delete",
+            DuplicateAction::Link => "link",
+        }
+    }
+}
+
+impl KeepRule {
+    /// The index of the file to keep in `group`.
+    pub fn pick(self, group: &DuplicateGroup, left_root: &Path) -> usize {
+        let files = group.files.iter().enumerate();
+        let oldest = |(_, v): &(usize, &DuplicateFile)| v.modified;
+        let kept = match self {
+            KeepRule::Oldest => files.min_by_key(oldest),
+            KeepRule::ShortestPath => files.min_by_key(|(_, v)| v.path.as_os_str().len()),
+            KeepRule::LeftPane => files
+                .clone()
+                .filter(|(_, v)| v.path.starts_with(left_root))
+                .min_by_key(oldest)
+                .or_else(|| files.min_by_key(oldest)),
+        };
+        kept.map_or(0, |(index, _)| index)
+    }
+}
+
+/// Finds files with equal content below `roots`. Candidates are narrowed down
+/// by size, then by a hash of their start and last by a hash of everything.
+/// Links are not followed and hard links to the same file count as one.
+pub fn find(roots: &[PathBuf], cancelled: &AtomicBool) -> io::Result<Vec<DuplicateGroup>> {
+    let mut seen = HashSet::new();
+    let mut by_size: HashMap<u64, Vec<DuplicateFile>> = HashMap::new();
+    for root in roots {
+        walk::walk(
+            root,
+            &mut |path, metadata| {
+                if cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+                if metadata.is_file() && metadata.len() > 0 && seen.insert(get_id(path, metadata)) {
+                    by_size
+                        .entry(metadata.len())
+                        .or_default()
+                        .push(DuplicateFile {
+                            path: path.to_path_buf(),
+                            modified: metadata.modified().unwrap_or(UNIX_EPOCH),
+                        });
+                }
+            },
+            &mut |path, e| warn!("Failed to read {:?}: {}", path, e),
+        );
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+    }
+
+    let mut groups = Vec::new();
+    for (size, files) in by_size.into_iter().filter(|(_, v)| v.len() > 1) {
+        for files in split_by_hash(files, Some(PARTIAL_HASH_SIZE), cancelled)? {
+            // Small files were hashed completely already.
+            let files = match size > PARTIAL_HASH_SIZE {
+                true => split_by_hash(files, None, cancelled)?,
+                false => vec![files],
+            };
+            for mut files in files {
+                files.sort_by(|a, b| a.path.cmp(&b.path));
+                groups.push(DuplicateGroup { size, files });
+            }
+        }
+    }
+    groups.sort_by_key(|v| Reverse(v.wasted()));
+    Ok(groups)
+}
+
+/// Groups `files` by hash, dropping the ones without an equal.
+fn split_by_hash(
+    files: Vec<DuplicateFile>,
+    limit: Option<u64>,
+    cancelled: &AtomicBool,
+) -> io::Result<Vec<Vec<DuplicateFile>>> {
+    let mut by_hash: HashMap<u64, Vec<DuplicateFile>> = HashMap::new();
+    for file in files {
+        match hash::hash_file(&file.path, limit, cancelled) {
+            Ok(v) => by_hash.entry(v).or_default().push(file),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => return Err(e),
+            Err(e) => warn!("Failed to hash {:?}: {}", file.path, e),
+        }
+    }
+    Ok(by_hash.into_values().filter(|v| v.len() > 1).collect())
+}
+
+/// Identifies a file independent of the links pointing to it where the
+/// platform allows.
+#[cfg(unix)]
+fn get_id(_path: &Path, metadata: &Metadata) -> (u64, u64) {
+    use std::os::unix::fs::MetadataExt;
+
+    (metadata.dev(), metadata.ino())
+}
+
+#[cfg(windows)]
+fn get_id(path: &Path, _metadata: &Metadata) -> PathBuf {
+    path.to_path_buf()
+}
+
+/// Deletes or links `duplicate` after checking that it still has the content
+/// of `original`, as hashes can collide and files may have changed since the
+/// scan. Without an original every copy is marked and nothing is done.
+pub fn resolve(
+    duplicate: &Path,
+    original: Option<&Path>,
+    action: DuplicateAction,
+    cancelled: &AtomicBool,
+) -> io::Result<()> {
+    let original = original.ok_or_else(|| io::Error::other("every copy is marked"))?;
+    if !compare::same_content(original, duplicate, cancelled)? {
+        let message = format!("it no longer matches {:?}", original);
+        return Err(io::Error::other(message));
+    }
+    match action {
+        DuplicateAction::Delete => fs::remove_file(duplicate),
+        DuplicateAction::Link => link_duplicate(original, duplicate),
+    }
+}
+
+/// Replaces `duplicate` with a hard link to `original`. The link is created
+/// next to it first, so the duplicate stays in place if linking fails.
+fn link_duplicate(original: &Path, duplicate: &Path) -> io::Result<()> {
+    let temporary = entry::temporary_path(duplicate, "diode-link")?;
+
+    fs::hard_link(original, &temporary)?;
+    fs::rename(&temporary, duplicate).inspect_err(|_| {
+        let _ = fs::remove_file(&temporary);
+    })
+}
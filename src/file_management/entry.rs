@@ -1,10 +1,13 @@
 use std::{
+    ffi::OsString,
     fs::{self, DirEntry},
     io,
-    path::Path,
+    path::{Path, PathBuf},
 };
 
-use crate::file_management::{directory::Directory, file::File};
+use log::warn;
+
+use crate::file_management::{directory::Directory, file::File, path, times, xattrs};
 
 #[derive(Debug)]
 pub enum Entry {
@@ -12,7 +15,87 @@ pub enum Entry {
     File(File),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinkKind {
+    Absolute,
+    Relative,
+    Hard,
+}
+
+/// Moves by renaming, falling back to copying and removing across devices.
 pub fn move_entry(current: &Path, destination: &Path) -> io::Result<()> {
+    let target = target_path(current, destination)?;
+    match fs::rename(current, &target) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            copy_entry(current, &target)?;
+            remove_entry(current)
+        }
+        result => result,
+    }
+}
+
+/// Removes a file, link or whole directory tree.
+pub fn remove_entry(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path)?.is_dir() {
+        true => fs::remove_dir_all(path),
+        false => fs::remove_file(path),
+    }
+}
+
+/// Copies `current` to `target` recursively, keeping permissions, times and
+/// extended attributes. Links are recreated instead of followed.
+pub fn copy_entry(current: &Path, target: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(current)?;
+    if metadata.is_symlink() {
+        return symlink(&fs::read_link(current)?, target);
+    }
+
+    if metadata.is_dir() {
+        fs::create_dir(target)?;
+        for entry in fs::read_dir(current)? {
+            let entry = entry?;
+            copy_entry(&entry.path(), &target.join(entry.file_name()))?;
+        }
+        fs::set_permissions(target, metadata.permissions())?;
+    } else {
+        fs::copy(current, target)?;
+    }
+    // Without the original times the copy would look newer than its source.
+    if let Err(e) = times::get_times(&metadata)
+        .and_then(|(accessed, modified)| times::set_times(target, accessed, modified))
+    {
+        warn!("Failed to keep the times of {:?}: {}", target, e);
+    }
+    xattrs::copy(current, target)
+}
+
+/// Links `current` into `destination`. Like a move, an existing file at the
+/// target is replaced while an existing directory is an error.
+pub fn link_entry(current: &Path, destination: &Path, kind: LinkKind) -> io::Result<()> {
+    if kind == LinkKind::Hard && fs::symlink_metadata(current)?.is_dir() {
+        return Err(io::Error::other("Directories cannot be hard linked"));
+    }
+
+    let target = target_path(current, destination)?;
+    let temporary = temporary_path(&target, "diode-link")?;
+    match kind {
+        LinkKind::Absolute => symlink(current, &temporary),
+        LinkKind::Relative => {
+            let directory = target
+                .parent()
+                .ok_or_else(|| io::Error::other("Link target has no parent"))?;
+            symlink(&path::relative(directory, current), &temporary)
+        }
+        LinkKind::Hard => fs::hard_link(current, &temporary),
+    }?;
+    fs::rename(&temporary, &target).inspect_err(|_| {
+        let _ = fs::remove_file(&temporary);
+    })
+}
+
+/// Resolves where `current` ends up when placed into `destination`, which may
+/// be a directory or a file inside the directory.
+pub fn target_path(current: &Path, destination: &Path) -> io::Result<PathBuf> {
     let dir = if destination.is_file() {
         let Some(dir) = destination.parent() else {
             return Err(io::Error::other("Destination is file but has no parent?"));
@@ -26,16 +109,53 @@ pub fn move_entry(current: &Path, destination: &Path) -> io::Result<()> {
         .file_name()
         .ok_or_else(|| io::Error::other("Source path has no filename"))?;
 
-    fs::rename(current, dir.join(filename))
+    Ok(dir.join(filename))
+}
+
+/// A hidden name next to `path` to prepare its replacement under, so the
+/// original stays in place until the replacement is complete.
+pub fn temporary_path(path: &Path, suffix: &str) -> io::Result<PathBuf> {
+    let mut name = OsString::from(".");
+    name.push(
+        path.file_name()
+            .ok_or_else(|| io::Error::other("Path has no filename"))?,
+    );
+    name.push(".");
+    name.push(suffix);
+    Ok(path.with_file_name(name))
+}
+
+#[cfg(unix)]
+fn symlink(original: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+#[cfg(windows)]
+fn symlink(original: &Path, link: &Path) -> io::Result<()> {
+    let is_dir = link
+        .parent()
+        .map(|v| v.join(original))
+        .is_some_and(|v| v.is_dir());
+    match is_dir {
+        true => std::os::windows::fs::symlink_dir(original, link),
+        false => std::os::windows::fs::symlink_file(original, link),
+    }
 }
 
 impl TryFrom<DirEntry> for Entry {
     type Error = io::Error;
 
     fn try_from(value: DirEntry) -> Result<Self, Self::Error> {
-        Ok(match value.file_type()?.is_dir() {
-            true => Entry::Directory(Directory::try_from(value.path())?),
-            false => Entry::File(File::try_from(value.path())?),
+        let path = value.path();
+        let file_type = value.file_type()?;
+        let is_dir = match file_type.is_symlink() {
+            true => fs::metadata(&path).is_ok_and(|v| v.is_dir()),
+            false => file_type.is_dir(),
+        };
+
+        Ok(match is_dir {
+            true => Entry::Directory(Directory::try_from(path)?),
+            false => Entry::File(File::try_from(path)?),
         })
     }
 }
@@ -5,12 +5,15 @@ use std::{
     path::PathBuf,
 };
 
+use crate::file_management::link::Link;
+
 #[derive(Debug, Clone)]
 pub struct File {
     pub name: OsString,
     pub path: PathBuf,
     #[allow(dead_code)]
     pub metadata: Metadata,
+    pub link: Option<Link>,
 }
 
 impl TryFrom<PathBuf> for File {
@@ -20,7 +23,9 @@ impl TryFrom<PathBuf> for File {
         Ok(Self {
             name: path.file_name().unwrap_or_default().to_owned(),
             path: path.to_path_buf(),
-            metadata: fs::metadata(path)?,
+            // Broken links have no target to describe, so fall back to the link itself.
+            metadata: fs::metadata(&path).or_else(|_| fs::symlink_metadata(&path))?,
+            link: Link::read(&path)?,
         })
     }
 }
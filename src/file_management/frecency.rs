@@ -0,0 +1,39 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone)]
+pub struct FrecencyEntry {
+    pub path: PathBuf,
+    pub rank: f64,
+    pub last_accessed: u64,
+}
+
+pub fn load(file: &Path) -> io::Result<Vec<FrecencyEntry>> {
+    let content = match fs::read_to_string(file) {
+        Ok(v) => v,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    Ok(content
+        .lines()
+        .filter_map(|v| {
+            let mut parts = v.splitn(3, '\t');
+            Some(FrecencyEntry {
+                rank: parts.next()?.parse().ok()?,
+                last_accessed: parts.next()?.parse().ok()?,
+                path: PathBuf::from(parts.next()?),
+            })
+        })
+        .collect())
+}
+
+pub fn save(file: &Path, entries: &[FrecencyEntry]) -> io::Result<()> {
+    let content: String = entries
+        .iter()
+        .map(|v| format!("{}\t{}\t{}\n", v.rank, v.last_accessed, v.path.display()))
+        .collect();
+    fs::write(file, content)
+}
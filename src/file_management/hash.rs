@@ -0,0 +1,28 @@
+use std::{
+    fs,
+    hash::{DefaultHasher, Hasher},
+    io::{self, Read},
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Hashes the content of `path`, or only its first `limit` bytes. The hasher
+/// uses fixed keys, so results can be compared between files.
+pub fn hash_file(path: &Path, limit: Option<u64>, cancelled: &AtomicBool) -> io::Result<u64> {
+    let file = fs::File::open(path)?;
+    let mut reader = file.take(limit.unwrap_or(u64::MAX));
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = vec![0; BUFFER_SIZE];
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(io::Error::from(io::ErrorKind::Interrupted));
+        }
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(hasher.finish());
+        }
+        hasher.write(&buffer[..read]);
+    }
+}
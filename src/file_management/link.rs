@@ -0,0 +1,35 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub target: PathBuf,
+    pub broken: bool,
+}
+
+impl Link {
+    pub fn read(path: &Path) -> io::Result<Option<Self>> {
+        if !fs::symlink_metadata(path)?.file_type().is_symlink() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            target: fs::read_link(path)?,
+            broken: fs::metadata(path).is_err(),
+        }))
+    }
+}
+
+/// A linked directory loops when it resolves to one of its own ancestors.
+pub fn is_loop(path: &Path) -> bool {
+    let Some(parent) = path.parent() else {
+        return false;
+    };
+
+    match (fs::canonicalize(path), fs::canonicalize(parent)) {
+        (Ok(target), Ok(parent)) => parent.starts_with(target),
+        _ => true,
+    }
+}
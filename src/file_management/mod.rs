@@ -1,3 +1,22 @@
+pub mod archive;
+pub mod bookmarks;
+pub mod compare;
+pub mod data;
+pub mod diff;
 pub mod directory;
+pub mod duplicates;
 pub mod entry;
 pub mod file;
+pub mod frecency;
+pub mod hash;
+pub mod link;
+pub mod ownership;
+pub mod path;
+pub mod pattern;
+pub mod permissions;
+pub mod sync;
+pub mod times;
+pub mod usage;
+pub mod walk;
+pub mod watcher;
+pub mod xattrs;
@@ -0,0 +1,126 @@
+use std::{fs::Metadata, io, path::Path};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OwnerChange {
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+}
+
+impl OwnerChange {
+    /// Parses `user`, `user:group` or `:group`, where both parts may be names or ids.
+    /// The older `user.group` form is accepted when no user has the whole name.
+    pub fn parse(input: &str) -> io::Result<Self> {
+        let input = input.trim();
+        let (user, group) = match input.split_once(':') {
+            Some((user, group)) => (user, Some(group)),
+            None => match input.split_once('.') {
+                Some((user, group)) if resolve_user(input).is_err() => (user, Some(group)),
+                _ => (input, None),
+            },
+        };
+
+        let uid = match user {
+            "" => None,
+            user => Some(resolve_user(user)?),
+        };
+        let gid = match group {
+            None | Some("") => None,
+            Some(group) => Some(resolve_group(group)?),
+        };
+        if uid.is_none() && gid.is_none() {
+            return Err(io::Error::other("Expected user, user:group or :group"));
+        }
+
+        Ok(Self { uid, gid })
+    }
+}
+
+/// Formats the owner of `metadata` as `user:group`, falling back to ids for unknown names.
+pub fn format(metadata: &Metadata) -> Option<String> {
+    let (uid, gid) = get_owner(metadata)?;
+    Some(format!("{}:{}", user_name(uid), group_name(gid)))
+}
+
+#[cfg(unix)]
+pub fn get_owner(metadata: &Metadata) -> Option<(u32, u32)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.uid(), metadata.gid()))
+}
+
+#[cfg(windows)]
+pub fn get_owner(_metadata: &Metadata) -> Option<(u32, u32)> {
+    None
+}
+
+/// Changes the owner of `path`, or of the link itself when `follow_link` is unset.
+#[cfg(unix)]
+pub fn change_owner(path: &Path, change: OwnerChange, follow_link: bool) -> io::Result<()> {
+    match follow_link {
+        true => std::os::unix::fs::chown(path, change.uid, change.gid),
+        false => std::os::unix::fs::lchown(path, change.uid, change.gid),
+    }
+}
+
+#[cfg(windows)]
+pub fn change_owner(_path: &Path, _change: OwnerChange, _follow_link: bool) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Changing ownership is not supported on this platform",
+    ))
+}
+
+#[cfg(unix)]
+fn resolve_user(user: &str) -> io::Result<u32> {
+    if let Ok(uid) = user.parse() {
+        return Ok(uid);
+    }
+    nix::unistd::User::from_name(user)?
+        .map(|v| v.uid.as_raw())
+        .ok_or_else(|| io::Error::other(format!("Unknown user {:?}", user)))
+}
+
+#[cfg(unix)]
+fn resolve_group(group: &str) -> io::Result<u32> {
+    if let Ok(gid) = group.parse() {
+        return Ok(gid);
+    }
+    nix::unistd::Group::from_name(group)?
+        .map(|v| v.gid.as_raw())
+        .ok_or_else(|| io::Error::other(format!("Unknown group {:?}", group)))
+}
+
+#[cfg(unix)]
+fn user_name(uid: u32) -> String {
+    match nix::unistd::User::from_uid(uid.into()) {
+        Ok(Some(v)) => v.name,
+        _ => uid.to_string(),
+    }
+}
+
+#[cfg(unix)]
+fn group_name(gid: u32) -> String {
+    match nix::unistd::Group::from_gid(gid.into()) {
+        Ok(Some(v)) => v.name,
+        _ => gid.to_string(),
+    }
+}
+
+#[cfg(windows)]
+fn resolve_user(user: &str) -> io::Result<u32> {
+    user.parse().map_err(io::Error::other)
+}
+
+#[cfg(windows)]
+fn resolve_group(group: &str) -> io::Result<u32> {
+    group.parse().map_err(io::Error::other)
+}
+
+#[cfg(windows)]
+fn user_name(uid: u32) -> String {
+    uid.to_string()
+}
+
+#[cfg(windows)]
+fn group_name(gid: u32) -> String {
+    gid.to_string()
+}
@@ -0,0 +1,132 @@
+use std::{
+    env, fs, io,
+    path::{Component, MAIN_SEPARATOR, Path, PathBuf},
+};
+
+pub fn expand(input: &str, base: &Path) -> PathBuf {
+    let expanded = expand_variables(input);
+    let expanded = match expanded.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', MAIN_SEPARATOR]) => {
+            match dirs::home_dir() {
+                Some(home) => format!("{}{}", home.display(), rest),
+                None => expanded,
+            }
+        }
+        _ => expanded,
+    };
+
+    base.join(expanded)
+}
+
+pub fn resolve(input: &str, base: &Path) -> io::Result<PathBuf> {
+    fs::canonicalize(expand(input, base))
+}
+
+/// Returns the names in the input's directory that start with its last component.
+pub fn complete(input: &str, base: &Path) -> Vec<String> {
+    let (directory, prefix) = match input.rfind(['/', MAIN_SEPARATOR]) {
+        Some(i) => (&input[..=i], &input[i + 1..]),
+        None => ("", input),
+    };
+
+    let Ok(entries) = fs::read_dir(expand(directory, base)) else {
+        return Vec::new();
+    };
+
+    let mut completions: Vec<String> = entries
+        .filter_map(|v| v.ok())
+        .filter_map(|v| {
+            let name = v.file_name().to_string_lossy().into_owned();
+            if !name.starts_with(prefix) || (prefix.is_empty() && name.starts_with('.')) {
+                return None;
+            }
+            Some(match v.path().is_dir() {
+                true => format!("{}{}/", directory, name),
+                false => format!("{}{}", directory, name),
+            })
+        })
+        .collect();
+    completions.sort();
+    completions
+}
+
+/// Builds the path that leads from `directory` to `target` using `..` where needed.
+pub fn relative(directory: &Path, target: &Path) -> PathBuf {
+    let directory: Vec<Component> = directory.components().collect();
+    let target: Vec<Component> = target.components().collect();
+    let shared = directory
+        .iter()
+        .zip(&target)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut result: PathBuf = directory[shared..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .collect();
+    result.extend(&target[shared..]);
+    result
+}
+
+/// Maps `path` below `from` to the same relative location below `to`.
+pub fn rebase(path: &Path, from: &Path, to: &Path) -> Option<PathBuf> {
+    path.strip_prefix(from).ok().map(|v| to.join(v))
+}
+
+pub fn common_prefix(values: &[String]) -> Option<String> {
+    let mut prefix = values.first()?.as_str();
+    for value in &values[1..] {
+        let length = prefix
+            .chars()
+            .zip(value.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        prefix = &prefix[..length];
+    }
+    Some(prefix.to_owned())
+}
+
+fn expand_variables(input: &str) -> String {
+    let mut result = String::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            result.push(c);
+            continue;
+        }
+
+        let braced = chars.next_if_eq(&'{').is_some();
+        let mut name = String::new();
+        while let Some(c) = chars.next_if(|v| v.is_alphanumeric() || *v == '_') {
+            name.push(c);
+        }
+        if braced {
+            chars.next_if_eq(&'}');
+        }
+
+        match env::var(&name) {
+            Ok(value) if !name.is_empty() => result.push_str(&value),
+            _ if braced => result.push_str(&format!("${{{}}}", name)),
+            _ => result.push_str(&format!("${}", name)),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_paths_climb_to_the_shared_ancestor() {
+        let relative =
+            |directory: &str, target: &str| relative(Path::new(directory), Path::new(target));
+        assert_eq!(relative("/a/b", "/a/b/c"), PathBuf::from("c"));
+        assert_eq!(relative("/a/b", "/a/c/d"), PathBuf::from("../c/d"));
+        assert_eq!(relative("/a/b/c", "/x"), PathBuf::from("../../../x"));
+        assert_eq!(relative("/a/b", "/a/b"), PathBuf::new());
+    }
+}
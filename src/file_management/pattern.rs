@@ -0,0 +1,110 @@
+use std::{ffi::OsStr, io, path::Path};
+
+use globset::{Glob, GlobMatcher};
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PatternSyntax {
+    Glob,
+    Regex,
+}
+
+impl PatternSyntax {
+    pub fn toggle(self) -> Self {
+        match self {
+            PatternSyntax::Glob => PatternSyntax::Regex,
+            PatternSyntax::Regex => PatternSyntax::Glob,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PatternSyntax::Glob => "glob",
+            PatternSyntax::Regex => "regex",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Pattern {
+    matcher: Matcher,
+    match_path: bool,
+}
+
+#[derive(Debug)]
+enum Matcher {
+    Glob(GlobMatcher),
+    Regex(Regex),
+}
+
+impl Pattern {
+    pub fn new(input: &str, syntax: PatternSyntax) -> io::Result<Self> {
+        let matcher = match syntax {
+            PatternSyntax::Glob => Matcher::Glob(
+                Glob::new(input)
+                    .map_err(io::Error::other)?
+                    .compile_matcher(),
+            ),
+            PatternSyntax::Regex => Matcher::Regex(Regex::new(input).map_err(io::Error::other)?),
+        };
+
+        Ok(Self {
+            matcher,
+            // Patterns containing a separator are matched against the path
+            // relative to the pane root instead of the bare name.
+            match_path: input.contains('/'),
+        })
+    }
+
+    pub fn is_match(&self, name: &OsStr, relative_path: &Path) -> bool {
+        let subject = match self.match_path {
+            true => relative_path.as_os_str(),
+            false => name,
+        };
+
+        match &self.matcher {
+            Matcher::Glob(v) => v.is_match(subject),
+            Matcher::Regex(v) => v.is_match(&subject.to_string_lossy()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_match(input: &str, syntax: PatternSyntax, path: &str) -> bool {
+        let path = Path::new(path);
+        Pattern::new(input, syntax)
+            .unwrap()
+            .is_match(path.file_name().unwrap(), path)
+    }
+
+    #[test]
+    fn globs_match_the_name() {
+        assert!(is_match("*.rs", PatternSyntax::Glob, "src/main.rs"));
+        assert!(!is_match("*.rs", PatternSyntax::Glob, "src/main.rso"));
+        assert!(is_match("ma?n.*", PatternSyntax::Glob, "src/main.rs"));
+        assert!(!is_match("src", PatternSyntax::Glob, "src/main.rs"));
+    }
+
+    #[test]
+    fn patterns_with_separators_match_the_path() {
+        assert!(is_match("src/*.rs", PatternSyntax::Glob, "src/main.rs"));
+        assert!(!is_match("src/*.rs", PatternSyntax::Glob, "tests/main.rs"));
+        assert!(is_match("^src/", PatternSyntax::Regex, "src/main.rs"));
+    }
+
+    #[test]
+    fn regexes_search_the_name() {
+        assert!(is_match("ain", PatternSyntax::Regex, "src/main.rs"));
+        assert!(is_match(r"^main\.rs$", PatternSyntax::Regex, "src/main.rs"));
+        assert!(!is_match(r"\.toml$", PatternSyntax::Regex, "src/main.rs"));
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert!(Pattern::new("[", PatternSyntax::Glob).is_err());
+        assert!(Pattern::new("(", PatternSyntax::Regex).is_err());
+    }
+}
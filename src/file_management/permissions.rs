@@ -0,0 +1,239 @@
+use std::{
+    fs::{self, Metadata},
+    io,
+    path::Path,
+};
+
+pub const SETUID: u32 = 0o4000;
+pub const SETGID: u32 = 0o2000;
+pub const STICKY: u32 = 0o1000;
+
+const USER: u32 = 0o4700;
+const GROUP: u32 = 0o2070;
+const OTHER: u32 = 0o1007;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModeChange {
+    Absolute(u32),
+    Symbolic(Vec<Clause>),
+    /// Bits to set and bits to clear, leaving the others as they are.
+    Delta {
+        set: u32,
+        clear: u32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clause {
+    who: u32,
+    operator: char,
+    permissions: String,
+}
+
+impl ModeChange {
+    /// Parses octal (`755`) or symbolic (`u+x,go-w`) input the way chmod does.
+    pub fn parse(input: &str) -> io::Result<Self> {
+        let input = input.trim();
+        if !input.is_empty() && input.chars().all(|c| c.is_digit(8)) {
+            return match u32::from_str_radix(input, 8) {
+                Ok(v) if v <= 0o7777 => Ok(ModeChange::Absolute(v)),
+                _ => Err(io::Error::other("Octal mode out of range")),
+            };
+        }
+
+        input
+            .split(',')
+            .map(parse_clause)
+            .collect::<io::Result<Vec<_>>>()
+            .map(ModeChange::Symbolic)
+    }
+
+    pub fn apply(&self, mode: u32, is_dir: bool) -> u32 {
+        let clauses = match self {
+            ModeChange::Absolute(v) => return *v,
+            ModeChange::Delta { set, clear } => return (mode & 0o7777 & !clear) | set,
+            ModeChange::Symbolic(v) => v,
+        };
+
+        clauses.iter().fold(mode & 0o7777, |mode, clause| {
+            let mut bits = 0;
+            for c in clause.permissions.chars() {
+                bits |= match c {
+                    'r' => 0o444,
+                    'w' => 0o222,
+                    'x' => 0o111,
+                    'X' if is_dir || mode & 0o111 != 0 => 0o111,
+                    's' => SETUID | SETGID,
+                    't' => STICKY,
+                    _ => 0,
+                };
+            }
+            let bits = bits & clause.who;
+
+            match clause.operator {
+                '+' => mode | bits,
+                '-' => mode & !bits,
+                // Directories keep their setuid/setgid bits unless named, as with chmod.
+                _ if is_dir => (mode & !(clause.who & 0o777)) | bits,
+                _ => (mode & !clause.who) | bits,
+            }
+        })
+    }
+}
+
+fn parse_clause(clause: &str) -> io::Result<Clause> {
+    let invalid = || io::Error::other(format!("Invalid mode clause {:?}", clause));
+
+    let split = clause.find(['+', '-', '=']).ok_or_else(invalid)?;
+    let (who, rest) = clause.split_at(split);
+
+    let who = match who {
+        "" => USER | GROUP | OTHER,
+        who => who.chars().try_fold(0, |acc, c| match c {
+            'u' => Ok(acc | USER),
+            'g' => Ok(acc | GROUP),
+            'o' => Ok(acc | OTHER),
+            'a' => Ok(acc | USER | GROUP | OTHER),
+            _ => Err(invalid()),
+        })?,
+    };
+
+    let mut chars = rest.chars();
+    let operator = chars.next().ok_or_else(invalid)?;
+    let permissions: String = chars.collect();
+    if !permissions.chars().all(|c| "rwxXst".contains(c)) {
+        return Err(invalid());
+    }
+
+    Ok(Clause {
+        who,
+        operator,
+        permissions,
+    })
+}
+
+/// Renders a mode as `rwxr-xr-x`, using `s`/`t` for the special bits like `ls`.
+pub fn format(mode: u32) -> String {
+    let mut text = String::with_capacity(9);
+    for (shift, special, special_char) in [(6, SETUID, 's'), (3, SETGID, 's'), (0, STICKY, 't')] {
+        let bits = (mode >> shift) & 0o7;
+        text.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        text.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        text.push(match (mode & special != 0, bits & 0o1 != 0) {
+            (true, true) => special_char,
+            (true, false) => special_char.to_ascii_uppercase(),
+            (false, true) => 'x',
+            (false, false) => '-',
+        });
+    }
+    text
+}
+
+#[cfg(unix)]
+pub fn get_mode(metadata: &Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(windows)]
+pub fn get_mode(metadata: &Metadata) -> u32 {
+    let mode = match metadata.permissions().readonly() {
+        true => 0o444,
+        false => 0o666,
+    };
+    match metadata.is_dir() {
+        true => mode | 0o111,
+        false => mode,
+    }
+}
+
+#[cfg(unix)]
+pub fn set_mode(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+}
+
+/// Windows only knows the read-only flag, which follows the write bits.
+#[cfg(windows)]
+pub fn set_mode(path: &Path, mode: u32) -> io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_readonly(mode & 0o222 == 0);
+    fs::set_permissions(path, permissions)
+}
+
+pub fn change_mode(path: &Path, change: &ModeChange) -> io::Result<()> {
+    let metadata = fs::metadata(path)?;
+    set_mode(path, change.apply(get_mode(&metadata), metadata.is_dir()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(input: &str, mode: u32, is_dir: bool) -> u32 {
+        ModeChange::parse(input).unwrap().apply(mode, is_dir)
+    }
+
+    #[test]
+    fn parses_octal_modes() {
+        assert_eq!(
+            ModeChange::parse("755").unwrap(),
+            ModeChange::Absolute(0o755)
+        );
+        assert_eq!(
+            ModeChange::parse(" 4755 ").unwrap(),
+            ModeChange::Absolute(0o4755)
+        );
+        assert!(ModeChange::parse("17777").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_clauses() {
+        assert!(ModeChange::parse("").is_err());
+        assert!(ModeChange::parse("u").is_err());
+        assert!(ModeChange::parse("z+x").is_err());
+        assert!(ModeChange::parse("u+q").is_err());
+        assert!(ModeChange::parse("u+x,").is_err());
+    }
+
+    #[test]
+    fn applies_symbolic_clauses() {
+        assert_eq!(apply("u+x", 0o644, false), 0o744);
+        assert_eq!(apply("go-r", 0o644, false), 0o600);
+        assert_eq!(apply("+x", 0o644, false), 0o755);
+        assert_eq!(apply("a=r", 0o777, false), 0o444);
+        assert_eq!(apply("u+x,g=rw,o-r", 0o644, false), 0o760);
+        assert_eq!(apply("u+s,+t", 0o755, false), 0o5755);
+    }
+
+    #[test]
+    fn applies_conditional_execute() {
+        assert_eq!(apply("a+X", 0o644, false), 0o644);
+        assert_eq!(apply("a+X", 0o744, false), 0o755);
+        assert_eq!(apply("a+X", 0o644, true), 0o755);
+    }
+
+    #[test]
+    fn keeps_directory_setgid_on_assignment() {
+        assert_eq!(apply("g=rx", 0o2775, true), 0o2755);
+        assert_eq!(apply("g=rx", 0o2775, false), 0o755);
+    }
+
+    #[test]
+    fn applies_deltas_to_each_mode() {
+        let change = ModeChange::Delta {
+            set: 0o100,
+            clear: 0o002,
+        };
+        assert_eq!(change.apply(0o646, false), 0o744);
+        assert_eq!(change.apply(0o600, false), 0o700);
+    }
+
+    #[test]
+    fn formats_special_bits() {
+        assert_eq!(format(0o755), "rwxr-xr-x");
+        assert_eq!(format(0o4755), "rwsr-xr-x");
+        assert_eq!(format(0o2745), "rwxr-Sr-x");
+        assert_eq!(format(0o1777), "rwxrwxrwt");
+    }
+}
@@ -0,0 +1,381 @@
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fs::{self, Metadata},
+    io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::file_management::{
+    compare::{self, Comparison},
+    entry,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncMode {
+    /// Copies new and changed entries from the source.
+    Mirror,
+    /// Mirrors and removes what the source does not have.
+    MirrorDelete,
+    /// Copies both ways, the newer side wins on conflicts.
+    TwoWay,
+}
+
+impl SyncMode {
+    pub fn next(self) -> Self {
+        match self {
+            SyncMode::Mirror => SyncMode::MirrorDelete,
+            SyncMode::MirrorDelete => SyncMode::TwoWay,
+            SyncMode::TwoWay => SyncMode::Mirror,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SyncMode::Mirror => "mirror",
+            SyncMode::MirrorDelete => "mirror, delete extras",
+            SyncMode::TwoWay => "two-way, newest wins",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SyncAction {
+    Copy {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Replace {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Delete(PathBuf),
+    /// A file on one side is a directory on the other. Neither is touched.
+    Conflict(PathBuf),
+}
+
+impl SyncAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SyncAction::Copy { .. } => "copy",
+            SyncAction::Replace { .. } => "replace",
+            SyncAction::Delete(_) => "delete",
+            SyncAction::Conflict(_) => "conflict",
+        }
+    }
+
+    /// The path that is created, overwritten or removed.
+    pub fn target(&self) -> &Path {
+        match self {
+            SyncAction::Copy { to, .. } | SyncAction::Replace { to, .. } => to,
+            SyncAction::Delete(path) | SyncAction::Conflict(path) => path,
+        }
+    }
+
+    pub fn execute(&self) -> io::Result<()> {
+        match self {
+            SyncAction::Copy { from, to } => entry::copy_entry(from, to),
+            SyncAction::Replace { from, to } => replace_entry(from, to),
+            SyncAction::Delete(path) => entry::remove_entry(path),
+            SyncAction::Conflict(_) => Ok(()),
+        }
+    }
+}
+
+/// Copies next to the target first and renames over it, so the target is
+/// kept when copying fails halfway.
+fn replace_entry(from: &Path, to: &Path) -> io::Result<()> {
+    let temporary = entry::temporary_path(to, "diode-sync")?;
+    entry::copy_entry(from, &temporary)
+        .and_then(|_| fs::rename(&temporary, to))
+        .inspect_err(|_| {
+            let _ = entry::remove_entry(&temporary);
+        })
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SyncSummary {
+    pub copied: usize,
+    pub replaced: usize,
+    pub deleted: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl SyncSummary {
+    pub fn record(&mut self, action: &SyncAction, result: &io::Result<()>) {
+        let count = match (action, result) {
+            (_, Err(_)) => &mut self.failed,
+            (SyncAction::Copy { .. }, _) => &mut self.copied,
+            (SyncAction::Replace { .. }, _) => &mut self.replaced,
+            (SyncAction::Delete(_), _) => &mut self.deleted,
+            (SyncAction::Conflict(_), _) => &mut self.skipped,
+        };
+        *count += 1;
+    }
+}
+
+/// Plans what makes `destination` match `source`, or both match each other in
+/// two-way mode. Directories on both sides are descended into, everything
+/// else is copied or replaced as a whole. A directory facing a file is left
+/// alone and reported as a conflict.
+pub fn plan(
+    source: &Path,
+    destination: &Path,
+    mode: SyncMode,
+    cancelled: &AtomicBool,
+) -> io::Result<Vec<SyncAction>> {
+    if source.starts_with(destination) || destination.starts_with(source) {
+        return Err(io::Error::other("Cannot sync a directory with itself"));
+    }
+
+    let mut actions = Vec::new();
+    plan_directory(source, destination, mode, cancelled, &mut actions)?;
+    Ok(actions)
+}
+
+fn plan_directory(
+    source: &Path,
+    destination: &Path,
+    mode: SyncMode,
+    cancelled: &AtomicBool,
+    actions: &mut Vec<SyncAction>,
+) -> io::Result<()> {
+    if cancelled.load(Ordering::Relaxed) {
+        return Err(io::ErrorKind::Interrupted.into());
+    }
+
+    let source_entries = read_entries(source)?;
+    let destination_entries = read_entries(destination)?;
+
+    for (name, source_metadata) in &source_entries {
+        let (from, to) = (source.join(name), destination.join(name));
+        let Some(destination_metadata) = destination_entries.get(name) else {
+            actions.push(SyncAction::Copy { from, to });
+            continue;
+        };
+
+        if source_metadata.is_dir() && destination_metadata.is_dir() {
+            plan_directory(&from, &to, mode, cancelled, actions)?;
+            continue;
+        }
+        if source_metadata.is_dir() != destination_metadata.is_dir() {
+            actions.push(SyncAction::Conflict(to));
+            continue;
+        }
+
+        match compare::compare(source_metadata, destination_metadata) {
+            Comparison::Identical => {}
+            Comparison::Older if mode == SyncMode::TwoWay => {
+                actions.push(SyncAction::Replace { from: to, to: from })
+            }
+            _ => actions.push(SyncAction::Replace { from, to }),
+        }
+    }
+
+    for name in destination_entries.keys() {
+        if source_entries.contains_key(name) {
+            continue;
+        }
+        let path = destination.join(name);
+        match mode {
+            SyncMode::Mirror => {}
+            SyncMode::MirrorDelete => actions.push(SyncAction::Delete(path)),
+            SyncMode::TwoWay => actions.push(SyncAction::Copy {
+                from: path,
+                to: source.join(name),
+            }),
+        }
+    }
+    Ok(())
+}
+
+/// Links are not followed, so a link to a directory is synced as a link.
+pub fn read_entries(directory: &Path) -> io::Result<BTreeMap<OsString, Metadata>> {
+    let mut entries = BTreeMap::new();
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        entries.insert(entry.file_name(), fs::symlink_metadata(entry.path())?);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::*;
+
+    /// A scratch directory with a `source` and a `destination` below it.
+    struct Roots(PathBuf);
+
+    impl Roots {
+        fn new(name: &str) -> Self {
+            let root =
+                std::env::temp_dir().join(format!("diode-sync-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(root.join("source")).unwrap();
+            fs::create_dir_all(root.join("destination")).unwrap();
+            Self(root)
+        }
+
+        fn source(&self) -> PathBuf {
+            self.0.join("source")
+        }
+
+        fn destination(&self) -> PathBuf {
+            self.0.join("destination")
+        }
+
+        /// Writes a file below the scratch directory, `age` seconds old.
+        fn write(&self, path: &str, content: &str, age: u64) {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, content).unwrap();
+            let modified = SystemTime::now() - Duration::from_secs(age);
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|v| v.set_modified(modified))
+                .unwrap();
+        }
+
+        fn plan(&self, mode: SyncMode) -> Vec<(&'static str, PathBuf)> {
+            plan(
+                &self.source(),
+                &self.destination(),
+                mode,
+                &AtomicBool::new(false),
+            )
+            .unwrap()
+            .iter()
+            .map(|v| {
+                (
+                    v.label(),
+                    v.target().strip_prefix(&self.0).unwrap().to_path_buf(),
+                )
+            })
+            .collect()
+        }
+    }
+
+    impl Drop for Roots {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn actions(expected: &[(&'static str, &str)]) -> Vec<(&'static str, PathBuf)> {
+        expected
+            .iter()
+            .map(|(label, path)| (*label, PathBuf::from(path)))
+            .collect()
+    }
+
+    #[test]
+    fn mirror_copies_and_replaces() {
+        let roots = Roots::new("mirror");
+        roots.write("source/new", "new", 10);
+        roots.write("source/changed", "new", 10);
+        roots.write("destination/changed", "old", 100);
+        roots.write("source/same", "same", 10);
+        roots.write("destination/same", "same", 10);
+        roots.write("source/nested/file", "new", 10);
+        roots.write("destination/extra", "extra", 10);
+
+        let mut planned = roots.plan(SyncMode::Mirror);
+        planned.sort();
+        assert_eq!(
+            planned,
+            actions(&[
+                ("copy", "destination/nested"),
+                ("copy", "destination/new"),
+                ("replace", "destination/changed"),
+            ])
+        );
+    }
+
+    #[test]
+    fn mirror_delete_removes_extras() {
+        let roots = Roots::new("mirror-delete");
+        roots.write("source/same", "same", 10);
+        roots.write("destination/same", "same", 10);
+        roots.write("destination/extra", "extra", 10);
+        roots.write("destination/directory/extra", "extra", 10);
+
+        let mut planned = roots.plan(SyncMode::MirrorDelete);
+        planned.sort();
+        assert_eq!(
+            planned,
+            actions(&[
+                ("delete", "destination/directory"),
+                ("delete", "destination/extra"),
+            ])
+        );
+    }
+
+    #[test]
+    fn two_way_lets_the_newer_side_win() {
+        let roots = Roots::new("two-way");
+        roots.write("source/newer", "new", 10);
+        roots.write("destination/newer", "old", 100);
+        roots.write("source/older", "old", 100);
+        roots.write("destination/older", "new", 10);
+        roots.write("destination/extra", "extra", 10);
+
+        let mut planned = roots.plan(SyncMode::TwoWay);
+        planned.sort();
+        assert_eq!(
+            planned,
+            actions(&[
+                ("copy", "source/extra"),
+                ("replace", "destination/newer"),
+                ("replace", "source/older"),
+            ])
+        );
+    }
+
+    #[test]
+    fn reports_type_conflicts() {
+        let roots = Roots::new("conflict");
+        roots.write("source/entry/file", "file", 10);
+        roots.write("destination/entry", "file", 10);
+
+        assert_eq!(
+            roots.plan(SyncMode::Mirror),
+            actions(&[("conflict", "destination/entry")])
+        );
+    }
+
+    #[test]
+    fn rejects_nested_roots() {
+        let roots = Roots::new("nested");
+        let cancelled = AtomicBool::new(false);
+        assert!(plan(&roots.source(), &roots.0, SyncMode::Mirror, &cancelled).is_err());
+        assert!(plan(&roots.0, &roots.source(), SyncMode::Mirror, &cancelled).is_err());
+    }
+
+    #[test]
+    fn replaces_files_in_place() {
+        let roots = Roots::new("replace");
+        roots.write("source/file", "new", 10);
+        roots.write("destination/file", "old", 100);
+
+        for action in plan(
+            &roots.source(),
+            &roots.destination(),
+            SyncMode::Mirror,
+            &AtomicBool::new(false),
+        )
+        .unwrap()
+        {
+            action.execute().unwrap();
+        }
+        assert_eq!(
+            fs::read_to_string(roots.destination().join("file")).unwrap(),
+            "new"
+        );
+        assert_eq!(fs::read_dir(roots.destination()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,111 @@
+use std::{fs::Metadata, io, path::Path, time::SystemTime};
+
+use jiff::{
+    Timestamp,
+    civil::{Date, DateTime},
+    tz::TimeZone,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TouchSource {
+    Now,
+    Date,
+    Other,
+}
+
+impl TouchSource {
+    pub fn next(self) -> Self {
+        match self {
+            TouchSource::Now => TouchSource::Date,
+            TouchSource::Date => TouchSource::Other,
+            TouchSource::Other => TouchSource::Now,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TouchSource::Now => "now",
+            TouchSource::Date => "typed date",
+            TouchSource::Other => "other pane",
+        }
+    }
+}
+
+/// Parses `2024-05-01`, `2024-05-01 13:30[:00]` in the local time zone, or a
+/// timestamp with an explicit offset such as `2024-05-01T13:30:00Z`.
+pub fn parse(input: &str) -> io::Result<SystemTime> {
+    let input = input.trim();
+    if let Ok(timestamp) = input.parse::<Timestamp>() {
+        return Ok(timestamp.into());
+    }
+
+    let date_time = match input.parse::<DateTime>() {
+        Ok(v) => v,
+        Err(_) => input
+            .parse::<Date>()
+            .map_err(io::Error::other)?
+            .to_datetime(jiff::civil::Time::midnight()),
+    };
+    let zoned = date_time
+        .to_zoned(TimeZone::system())
+        .map_err(io::Error::other)?;
+    Ok(zoned.timestamp().into())
+}
+
+pub fn format(time: SystemTime) -> String {
+    match Timestamp::try_from(time) {
+        Ok(v) => v
+            .to_zoned(TimeZone::system())
+            .strftime("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        Err(_) => "unknown".to_owned(),
+    }
+}
+
+pub fn get_times(metadata: &Metadata) -> io::Result<(SystemTime, SystemTime)> {
+    Ok((metadata.accessed()?, metadata.modified()?))
+}
+
+#[cfg(unix)]
+pub fn set_times(path: &Path, accessed: SystemTime, modified: SystemTime) -> io::Result<()> {
+    use std::time::UNIX_EPOCH;
+
+    use nix::{
+        fcntl::AT_FDCWD,
+        sys::{
+            stat::{UtimensatFlags, utimensat},
+            time::TimeSpec,
+        },
+    };
+
+    let to_spec = |time: SystemTime| {
+        time.duration_since(UNIX_EPOCH)
+            .map(TimeSpec::from)
+            .map_err(|_| io::Error::other("Times before 1970 are not supported"))
+    };
+    utimensat(
+        AT_FDCWD,
+        path,
+        &to_spec(accessed)?,
+        &to_spec(modified)?,
+        UtimensatFlags::FollowSymlink,
+    )
+    .map_err(io::Error::from)
+}
+
+#[cfg(windows)]
+pub fn set_times(path: &Path, accessed: SystemTime, modified: SystemTime) -> io::Result<()> {
+    use std::{fs::FileTimes, os::windows::fs::OpenOptionsExt};
+
+    // Directories can only be opened with backup semantics.
+    const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x02000000;
+    std::fs::File::options()
+        .write(true)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)?
+        .set_times(
+            FileTimes::new()
+                .set_accessed(accessed)
+                .set_modified(modified),
+        )
+}
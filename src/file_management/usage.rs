@@ -0,0 +1,164 @@
+use std::{
+    ffi::OsString,
+    fs::{self, Metadata},
+    io,
+    path::{Component, Path},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Usage {
+    pub size: u64,
+    pub files: u64,
+}
+
+/// Sums the apparent size and number of files below `path` without following
+/// links. Unreadable subdirectories are skipped so one of them does not hide
+/// the size of everything else.
+pub fn scan(path: &Path, cancelled: &AtomicBool) -> io::Result<Usage> {
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok(Usage {
+            size: metadata.len(),
+            files: 1,
+        });
+    }
+
+    let mut usage = Usage::default();
+    let mut directories = vec![path.to_path_buf()];
+    let mut first = true;
+    while let Some(directory) = directories.pop() {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(io::Error::from(io::ErrorKind::Interrupted));
+        }
+
+        let read_dir = match fs::read_dir(&directory) {
+            Ok(v) => v,
+            Err(e) if first => return Err(e),
+            Err(_) => continue,
+        };
+        first = false;
+
+        for entry in read_dir.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                directories.push(entry.path());
+            } else {
+                usage.size += metadata.len();
+                usage.files += 1;
+            }
+        }
+    }
+    Ok(usage)
+}
+
+/// A scanned entry with the summed usage of everything below it.
+#[derive(Debug)]
+pub struct UsageNode {
+    pub name: OsString,
+    pub usage: Usage,
+    pub is_dir: bool,
+    /// Sorted from largest to smallest.
+    pub children: Vec<UsageNode>,
+}
+
+impl UsageNode {
+    /// Looks up a descendant by its path relative to this node.
+    pub fn find(&self, relative: &Path) -> Option<&UsageNode> {
+        relative.components().try_fold(self, |node, component| {
+            let Component::Normal(name) = component else {
+                return None;
+            };
+            node.children.iter().find(|v| v.name == name)
+        })
+    }
+
+    /// Removes a descendant and subtracts its usage from every node above it.
+    pub fn remove(&mut self, relative: &Path) -> Option<Usage> {
+        let mut components = relative.components();
+        let Some(Component::Normal(name)) = components.next() else {
+            return None;
+        };
+        let index = self.children.iter().position(|v| v.name == name)?;
+
+        let rest = components.as_path();
+        let removed = match rest.as_os_str().is_empty() {
+            true => self.children.remove(index).usage,
+            false => self.children[index].remove(rest)?,
+        };
+        self.usage.size -= removed.size;
+        self.usage.files -= removed.files;
+        self.sort_children();
+        Some(removed)
+    }
+
+    fn sort_children(&mut self) {
+        self.children
+            .sort_by(|a, b| b.usage.size.cmp(&a.usage.size).then(a.name.cmp(&b.name)));
+    }
+}
+
+/// Builds the usage tree below `path` without following links.
+pub fn scan_tree(path: &Path, cancelled: &AtomicBool) -> io::Result<UsageNode> {
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        // Only the root has to be readable, deeper failures are skipped.
+        fs::read_dir(path)?;
+    }
+    let name = path.file_name().unwrap_or(path.as_os_str()).to_owned();
+    scan_node(path, name, &metadata, cancelled)
+}
+
+fn scan_node(
+    path: &Path,
+    name: OsString,
+    metadata: &Metadata,
+    cancelled: &AtomicBool,
+) -> io::Result<UsageNode> {
+    if cancelled.load(Ordering::Relaxed) {
+        return Err(io::Error::from(io::ErrorKind::Interrupted));
+    }
+
+    let mut node = UsageNode {
+        name,
+        usage: Usage::default(),
+        is_dir: metadata.is_dir(),
+        children: Vec::new(),
+    };
+    if !node.is_dir {
+        node.usage = Usage {
+            size: metadata.len(),
+            files: 1,
+        };
+        return Ok(node);
+    }
+
+    for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let child = scan_node(&entry.path(), entry.file_name(), &metadata, cancelled)?;
+        node.usage.size += child.usage.size;
+        node.usage.files += child.usage.files;
+        node.children.push(child);
+    }
+    node.sort_children();
+    Ok(node)
+}
+
+pub fn format_size(size: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if size < 1024 {
+        return format!("{} B", size);
+    }
+
+    let mut value = size as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
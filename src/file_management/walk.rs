@@ -0,0 +1,59 @@
+use std::{
+    fs::{self, Metadata},
+    io,
+    path::Path,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecurseTarget {
+    All,
+    Files,
+    Directories,
+}
+
+impl RecurseTarget {
+    pub fn includes(self, is_dir: bool) -> bool {
+        match self {
+            RecurseTarget::All => true,
+            RecurseTarget::Files => !is_dir,
+            RecurseTarget::Directories => is_dir,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RecurseTarget::All => "all",
+            RecurseTarget::Files => "files",
+            RecurseTarget::Directories => "directories",
+        }
+    }
+}
+
+/// Calls `on_entry` with the link metadata of `path` and, when it is a
+/// directory that is not a link, of everything below it. Errors are handed to
+/// `on_error` so one unreadable directory does not stop the rest of the walk.
+pub fn walk(
+    path: &Path,
+    on_entry: &mut impl FnMut(&Path, &Metadata),
+    on_error: &mut impl FnMut(&Path, io::Error),
+) {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(v) => v,
+        Err(e) => return on_error(path, e),
+    };
+    on_entry(path, &metadata);
+    if !metadata.is_dir() {
+        return;
+    }
+
+    let read_dir = match fs::read_dir(path) {
+        Ok(v) => v,
+        Err(e) => return on_error(path, e),
+    };
+    for entry in read_dir {
+        match entry {
+            Ok(v) => walk(&v.path(), on_entry, on_error),
+            Err(e) => on_error(path, e),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use log::error;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::event::AppEvent;
+
+#[derive(Debug)]
+pub struct DirectoryWatcher {
+    watcher: RecommendedWatcher,
+    watched: BTreeSet<PathBuf>,
+}
+
+impl DirectoryWatcher {
+    pub fn try_new(sender: UnboundedSender<AppEvent>) -> notify::Result<Self> {
+        let watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let event = match event {
+                Ok(v) => v,
+                Err(e) => {
+                    error!("Watch error: {}", e);
+                    return;
+                }
+            };
+
+            if matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_)
+            ) {
+                let _ = sender.send(AppEvent::FileSystemChanged(event.paths));
+            }
+        })?;
+
+        Ok(Self {
+            watcher,
+            watched: BTreeSet::new(),
+        })
+    }
+
+    pub fn update(&mut self, directories: BTreeSet<PathBuf>) {
+        for path in self.watched.difference(&directories) {
+            if let Err(e) = self.watcher.unwatch(path) {
+                error!("Failed to unwatch {:?}: {}", path, e);
+            }
+        }
+
+        for path in directories.difference(&self.watched) {
+            if let Err(e) = self.watcher.watch(path, RecursiveMode::NonRecursive) {
+                error!("Failed to watch {:?}: {}", path, e);
+            }
+        }
+
+        self.watched = directories;
+    }
+}
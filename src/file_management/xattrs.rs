@@ -0,0 +1,94 @@
+use std::{ffi::OsString, io, path::Path};
+
+pub const USER_NAMESPACE: &str = "user.";
+
+/// Only the user namespace is editable, so bare names are placed in it.
+pub fn to_user_name(name: &str) -> String {
+    match name.starts_with(USER_NAMESPACE) {
+        true => name.to_owned(),
+        false => format!("{}{}", USER_NAMESPACE, name),
+    }
+}
+
+/// Renders a value as text when it is printable, otherwise by its size.
+pub fn format_value(value: &[u8]) -> String {
+    let value = value.strip_suffix(b"\0").unwrap_or(value);
+    match std::str::from_utf8(value) {
+        Ok(v) if !v.chars().any(char::is_control) => v.to_owned(),
+        _ => format!("<{} bytes>", value.len()),
+    }
+}
+
+#[cfg(unix)]
+pub fn list(path: &Path) -> io::Result<Vec<(OsString, Vec<u8>)>> {
+    let mut attributes = Vec::new();
+    for name in xattr::list_deref(path)? {
+        if let Some(value) = xattr::get_deref(path, &name)? {
+            attributes.push((name, value));
+        }
+    }
+    Ok(attributes)
+}
+
+#[cfg(unix)]
+pub fn set(path: &Path, name: &str, value: &[u8]) -> io::Result<()> {
+    xattr::set_deref(path, to_user_name(name), value)
+}
+
+#[cfg(unix)]
+pub fn remove(path: &Path, name: &str) -> io::Result<()> {
+    xattr::remove_deref(path, to_user_name(name))
+}
+
+/// Copies every attribute the destination accepts. This includes ACLs, which
+/// are stored as `system.posix_acl_*` attributes, so it has to run after the
+/// permissions were set.
+#[cfg(unix)]
+pub fn copy(from: &Path, to: &Path) -> io::Result<()> {
+    use log::warn;
+
+    let names = match xattr::list(from) {
+        Ok(v) => v,
+        Err(e) if e.kind() == io::ErrorKind::Unsupported => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    for name in names {
+        let Some(value) = xattr::get(from, &name)? else {
+            continue;
+        };
+        // A missing attribute should not fail the whole copy.
+        if let Err(e) = xattr::set(to, &name, &value) {
+            warn!("Failed to copy {:?} to {:?}: {}", name, to, e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn list(_path: &Path) -> io::Result<Vec<(OsString, Vec<u8>)>> {
+    Ok(Vec::new())
+}
+
+#[cfg(windows)]
+pub fn set(_path: &Path, _name: &str, _value: &[u8]) -> io::Result<()> {
+    Err(unsupported())
+}
+
+#[cfg(windows)]
+pub fn remove(_path: &Path, _name: &str) -> io::Result<()> {
+    Err(unsupported())
+}
+
+#[cfg(windows)]
+pub fn copy(_from: &Path, _to: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(windows)]
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Extended attributes are not supported on this platform",
+    )
+}
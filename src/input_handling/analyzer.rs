@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use log::error;
+
+use crate::{
+    app::App,
+    file_management::entry,
+    state::diode::{
+        explorer_state::ExplorerState,
+        prompt_state::{PromptKind, PromptState},
+    },
+};
+
+pub fn on_key_event(app: &mut App, key: KeyEvent) {
+    let (current_state, other_state) = app.diode_state.get_states_mut();
+    let Some(analyzer) = current_state.analyzer.as_mut() else {
+        return;
+    };
+
+    match (key.modifiers, key.code) {
+        (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => app.quit(),
+        (_, KeyCode::Esc | KeyCode::Char('U')) => current_state.analyzer = None,
+        (_, KeyCode::Tab) => app.diode_state.switch_selection(),
+        (_, KeyCode::Char('j') | KeyCode::Down) => analyzer.move_by(1),
+        (_, KeyCode::Char('k') | KeyCode::Up) => analyzer.move_by(-1),
+        (_, KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right) => analyzer.enter(),
+        (_, KeyCode::Backspace | KeyCode::Char('h') | KeyCode::Left) => analyzer.leave(),
+        (_, KeyCode::Char(' ')) => analyzer.toggle_marked(),
+        (_, KeyCode::Char('R')) => analyzer.rescan(),
+        (_, KeyCode::Char('d')) => {
+            let count = analyzer.get_marked_roots().len();
+            if count > 0 {
+                let kind = PromptKind::Delete { count };
+                app.diode_state.prompt = Some(PromptState::new(kind));
+            }
+        }
+        (_, KeyCode::Char('m')) => move_marked(current_state, other_state),
+        _ => {}
+    }
+}
+
+pub fn delete_marked(state: &mut ExplorerState) {
+    let Some(analyzer) = &mut state.analyzer else {
+        return;
+    };
+
+    let paths = analyzer.get_marked_roots();
+    for path in &paths {
+        match entry::remove_entry(path) {
+            Ok(()) => analyzer.remove(path),
+            Err(e) => error!("Failed to delete {:?}: {}", path, e),
+        }
+    }
+    state.sync_directories(
+        paths
+            .iter()
+            .filter_map(|v| v.parent())
+            .map(Path::to_path_buf),
+    );
+}
+
+fn move_marked(current_state: &mut ExplorerState, other_state: &mut ExplorerState) {
+    let Some(analyzer) = &mut current_state.analyzer else {
+        return;
+    };
+
+    let destination = other_state.root.directory.path.clone();
+    let paths = analyzer.get_marked_roots();
+    for path in &paths {
+        match entry::move_entry(path, &destination) {
+            Ok(()) => analyzer.remove(path),
+            Err(e) => error!("Failed to move {:?}: {}", path, e),
+        }
+    }
+    current_state.sync_directories(
+        paths
+            .iter()
+            .filter_map(|v| v.parent())
+            .map(Path::to_path_buf),
+    );
+    other_state.sync_directories([destination]);
+}
@@ -0,0 +1,50 @@
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::error;
+
+use crate::{app::App, state::diode::explorer_state::ExplorerState};
+
+pub fn on_key_event(app: &mut App, key: KeyEvent) {
+    let bookmarks = &mut app.diode_state.bookmarks;
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('B') => bookmarks.close_picker(),
+        KeyCode::Char('j') | KeyCode::Down => bookmarks.picker_next(),
+        KeyCode::Char('k') | KeyCode::Up => bookmarks.picker_previous(),
+        KeyCode::Char('d') => {
+            if let Some((name, _)) = bookmarks.get_picked() {
+                let name = name.clone();
+                bookmarks.remove(&name);
+            }
+        }
+        KeyCode::Enter | KeyCode::Char('o') => {
+            let Some((_, path)) = bookmarks.get_picked() else {
+                return;
+            };
+            let path = path.clone();
+            bookmarks.close_picker();
+
+            let (current_state, other_state) = app.diode_state.get_states_mut();
+            let state = match key.code {
+                KeyCode::Enter => current_state,
+                _ => other_state,
+            };
+            jump(state, &path);
+        }
+        _ => {}
+    }
+}
+
+pub fn on_quick_slot(app: &mut App, slot: char) {
+    let Some(path) = app.diode_state.bookmarks.get(&slot.to_string()).cloned() else {
+        return;
+    };
+    let (current_state, _) = app.diode_state.get_states_mut();
+    jump(current_state, &path);
+}
+
+fn jump(state: &mut ExplorerState, path: &Path) {
+    if let Err(e) = state.jump_to(path.to_path_buf()) {
+        error!("Failed to jump to {:?}: {}", path, e);
+    }
+}
@@ -0,0 +1,61 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use log::error;
+
+use crate::{
+    app::App,
+    state::diode::{diff_state::DiffState, diode_state::Selection},
+};
+
+/// Diffs the selected file against the selected file of the other pane, or
+/// against the file at the same relative path when nothing usable is selected there.
+pub fn open(app: &mut App) {
+    let current_state = app.diode_state.get_current_state();
+    let other_state = app.diode_state.get_other_state();
+    let Some(path) = current_state.selected.clone().filter(|v| v.is_file()) else {
+        error!("Only files can be diffed");
+        return;
+    };
+
+    let counterpart = other_state
+        .selected
+        .clone()
+        .filter(|v| v.is_file())
+        .or_else(|| {
+            let relative = path.strip_prefix(&current_state.root.directory.path).ok()?;
+            Some(other_state.root.directory.path.join(relative))
+        })
+        .filter(|v| v.is_file());
+    let Some(counterpart) = counterpart else {
+        error!("No file in the other pane to diff {:?} with", path);
+        return;
+    };
+
+    let (left, right) = match app.diode_state.selected {
+        Selection::Left => (path, counterpart),
+        Selection::Right => (counterpart, path),
+    };
+    app.diode_state.diff = Some(DiffState::new(left, right));
+}
+
+pub fn on_key_event(app: &mut App, key: KeyEvent) {
+    let Some(diff) = app.diode_state.diff.as_mut() else {
+        return;
+    };
+
+    match (key.modifiers, key.code) {
+        (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => app.quit(),
+        (KeyModifiers::CONTROL, KeyCode::Char('d')) => diff.scroll_half_page(true),
+        (KeyModifiers::CONTROL, KeyCode::Char('u')) => diff.scroll_half_page(false),
+        (_, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('=')) => app.diode_state.diff = None,
+        (_, KeyCode::Char('j') | KeyCode::Down) => diff.scroll_by(1),
+        (_, KeyCode::Char('k') | KeyCode::Up) => diff.scroll_by(-1),
+        (_, KeyCode::PageDown) => diff.scroll_by(diff.height as isize),
+        (_, KeyCode::PageUp) => diff.scroll_by(-(diff.height as isize)),
+        (_, KeyCode::Char('g') | KeyCode::Home) => diff.scroll_to_top(),
+        (_, KeyCode::Char('G') | KeyCode::End) => diff.scroll_to_bottom(),
+        (_, KeyCode::Char('n')) => diff.next_hunk(),
+        (_, KeyCode::Char('N')) => diff.previous_hunk(),
+        (_, KeyCode::Char('s')) => diff.toggle_layout(),
+        _ => {}
+    }
+}
@@ -0,0 +1,50 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    app::App,
+    file_management::duplicates::{DuplicateAction, KeepRule},
+    state::diode::{
+        diode_state::DiodeState,
+        prompt_state::{PromptKind, PromptState},
+    },
+};
+
+pub fn on_key_event(app: &mut App, key: KeyEvent) {
+    let Some(duplicates) = app.diode_state.duplicates.as_mut() else {
+        return;
+    };
+
+    match (key.modifiers, key.code) {
+        (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => app.quit(),
+        // Closing the view also cancels a running scan, delete or link.
+        (_, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('u')) => {
+            app.diode_state.duplicates = None
+        }
+        (_, KeyCode::Tab) => duplicates.toggle_both(),
+        (_, KeyCode::Char('j') | KeyCode::Down) => duplicates.move_by(1),
+        (_, KeyCode::Char('k') | KeyCode::Up) => duplicates.move_by(-1),
+        (_, KeyCode::Char(' ')) => duplicates.toggle_marked(),
+        (_, KeyCode::Char('R')) => duplicates.rescan(),
+        (_, KeyCode::Char('o')) => duplicates.mark_by_rule(KeepRule::Oldest),
+        (_, KeyCode::Char('s')) => duplicates.mark_by_rule(KeepRule::ShortestPath),
+        (_, KeyCode::Char('l')) => duplicates.mark_by_rule(KeepRule::LeftPane),
+        // Replaces the marked files with hard links to an unmarked file of their group.
+        (_, KeyCode::Char('h')) => duplicates.resolve(DuplicateAction::Link),
+        (_, KeyCode::Char('d')) => {
+            let count = duplicates.marked.len();
+            if count > 0 {
+                let kind = PromptKind::Delete { count };
+                app.diode_state.prompt = Some(PromptState::new(kind));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Deletes the marked files in the background. Groups where every file is
+/// marked are left alone, so one copy of each always remains.
+pub fn delete_marked(diode_state: &mut DiodeState) {
+    if let Some(duplicates) = &mut diode_state.duplicates {
+        duplicates.resolve(DuplicateAction::Delete);
+    }
+}
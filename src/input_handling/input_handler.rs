@@ -1,17 +1,91 @@
+use std::path::Path;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use log::error;
 
-use crate::{app::App, input_handling::directory, state::diode::selected_entry::SelectedEntry};
+use crate::{
+    app::App,
+    file_management::{entry::LinkKind, pattern::PatternSyntax, times::TouchSource},
+    input_handling::{
+        analyzer, bookmarks, diff, directory, duplicates, permissions, prompt, sync, visual,
+    },
+    state::diode::{
+        entry_state::EntryState,
+        explorer_state::ExplorerState,
+        prompt_state::{PromptKind, PromptState},
+        selected_entry::SelectedEntry,
+    },
+};
 
 pub fn on_key_event(app: &mut App, key: KeyEvent) {
+    if app.diode_state.prompt.is_some() {
+        prompt::on_key_event(app, key);
+        return;
+    }
+
+    if app.diode_state.permissions.is_some() {
+        permissions::on_key_event(app, key);
+        return;
+    }
+
+    if app.diode_state.sync.is_some() {
+        sync::on_key_event(app, key);
+        return;
+    }
+
+    if app.diode_state.diff.is_some() {
+        diff::on_key_event(app, key);
+        return;
+    }
+
+    if app.diode_state.duplicates.is_some() {
+        duplicates::on_key_event(app, key);
+        return;
+    }
+
+    if app.diode_state.bookmarks.picker.is_some() {
+        bookmarks::on_key_event(app, key);
+        return;
+    }
+
+    if let Some('\'') = app.diode_state.pending_key.take() {
+        if let KeyCode::Char(slot) = key.code {
+            bookmarks::on_quick_slot(app, slot);
+        }
+        return;
+    }
+
     let (current_state, other_state) = app.diode_state.get_states_mut();
+    if current_state.analyzer.is_some() {
+        analyzer::on_key_event(app, key);
+        return;
+    }
+
+    if current_state.visual_anchor.is_some() {
+        visual::on_key_event(key, current_state);
+        return;
+    }
+
     match (key.modifiers, key.code) {
         (_, KeyCode::Char('q'))
         | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => app.quit(),
         (_, KeyCode::Tab) => app.diode_state.switch_selection(),
+        (KeyModifiers::CONTROL, KeyCode::Char('d')) => current_state.move_half_page_down(),
+        (KeyModifiers::CONTROL, KeyCode::Char('u')) => current_state.move_half_page_up(),
+        (KeyModifiers::CONTROL, KeyCode::Char('f')) | (_, KeyCode::PageDown) => {
+            current_state.move_page_down()
+        }
+        (KeyModifiers::CONTROL, KeyCode::Char('b')) | (_, KeyCode::PageUp) => {
+            current_state.move_page_up()
+        }
+        (_, KeyCode::Char('g') | KeyCode::Home) => current_state.move_to_top(),
+        (_, KeyCode::Char('G') | KeyCode::End) => current_state.move_to_bottom(),
         (_, KeyCode::Char('j')) => current_state.move_down(),
         (_, KeyCode::Char('k')) => current_state.move_up(),
         (_, KeyCode::Backspace) => current_state.set_parent_as_new_root(),
+        (_, KeyCode::Char('R')) => current_state.refresh(),
+        (_, KeyCode::Char('H')) => current_state.go_back(),
+        (_, KeyCode::Char('L')) => current_state.go_forward(),
         (_, KeyCode::Esc) => current_state.clear_marked(),
         (_, KeyCode::Char('m')) => {
             let Some(selected) = &current_state.selected else {
@@ -28,9 +102,88 @@ pub fn on_key_event(app: &mut App, key: KeyEvent) {
 
             current_state.reload(old_entries, new_entries)
         }
+        (_, KeyCode::Char('s')) => link_marked(current_state, other_state, LinkKind::Absolute),
+        (_, KeyCode::Char('S')) => link_marked(current_state, other_state, LinkKind::Relative),
+        (_, KeyCode::Char('h')) => link_marked(current_state, other_state, LinkKind::Hard),
+        (_, KeyCode::Char('p')) => permissions::open(app),
+        (_, KeyCode::Char('t')) => {
+            let kind = PromptKind::Touch {
+                source: TouchSource::Now,
+            };
+            app.diode_state.prompt = Some(PromptState::new(kind));
+        }
+        (_, KeyCode::Char('d')) => {
+            let directories: Vec<_> = current_state
+                .get_marked_or_selected()
+                .into_iter()
+                .filter(|v| matches!(current_state.entries.get(v), Some(EntryState::Directory(_))))
+                .collect();
+            for directory in directories {
+                app.diode_state.usage.rescan(directory);
+            }
+        }
+        (_, KeyCode::Char('D')) => app.diode_state.usage.toggle_auto(),
+        (_, KeyCode::Char('U')) => current_state.toggle_analyzer(),
+        (_, KeyCode::Char('c')) => app.diode_state.toggle_compare(false),
+        (_, KeyCode::Char('C')) => app.diode_state.toggle_compare(true),
+        (_, KeyCode::Char('y')) => app.diode_state.open_sync(),
+        (_, KeyCode::Char('=')) => diff::open(app),
+        (_, KeyCode::Char('u')) => app.diode_state.open_duplicates(),
+        (_, KeyCode::Char('A')) => {
+            app.diode_state.prompt = Some(PromptState::new(PromptKind::Archive))
+        }
+        (_, KeyCode::Char('x')) => {
+            let kind = PromptKind::Xattr { remove: false };
+            app.diode_state.prompt = Some(PromptState::new(kind));
+        }
+        (_, KeyCode::Char('X')) => {
+            let kind = PromptKind::Xattr { remove: true };
+            app.diode_state.prompt = Some(PromptState::new(kind));
+        }
+        (_, KeyCode::Char('o')) => {
+            if let Some(entry) = current_state
+                .get_marked_or_selected()
+                .first()
+                .and_then(|v| current_state.entries.get_mut(v))
+            {
+                entry.update_details();
+            }
+            let kind = PromptKind::Ownership { recursive: false };
+            app.diode_state.prompt = Some(PromptState::new(kind));
+        }
         (_, KeyCode::Char(' ')) => {
             current_state.toggle_marked();
         }
+        (_, KeyCode::Char('+')) => {
+            app.diode_state.prompt = Some(PromptState::new(PromptKind::Mark {
+                marked: true,
+                syntax: PatternSyntax::Glob,
+            }))
+        }
+        (_, KeyCode::Char('-')) => {
+            app.diode_state.prompt = Some(PromptState::new(PromptKind::Mark {
+                marked: false,
+                syntax: PatternSyntax::Glob,
+            }))
+        }
+        (_, KeyCode::Char('b')) => {
+            app.diode_state.prompt = Some(PromptState::new(PromptKind::Bookmark))
+        }
+        (_, KeyCode::Char('B')) => app.diode_state.bookmarks.open_picker(),
+        (_, KeyCode::Char('\'')) => app.diode_state.pending_key = Some('\''),
+        (_, KeyCode::Char(':')) => {
+            app.diode_state.prompt = Some(PromptState::new(PromptKind::GoTo))
+        }
+        (_, KeyCode::Char('z')) => {
+            app.diode_state.prompt = Some(PromptState::new(PromptKind::Jump));
+            prompt::update_jump_target(&mut app.diode_state);
+        }
+        (_, KeyCode::Char('@')) => current_state.toggle_follow_links(),
+        (_, KeyCode::Char('V')) => current_state.start_visual(),
+        (_, KeyCode::Char('*')) => current_state.invert_marked(),
+        (_, KeyCode::Char('a')) => current_state.mark_current_directory(),
+        (_, KeyCode::Char('f')) => current_state.mark_files(),
+        (_, KeyCode::Char('F')) => current_state.mark_directories(),
         _ => {
             let Some(selected_state) = current_state.with_selected() else {
                 return;
@@ -41,3 +194,18 @@ pub fn on_key_event(app: &mut App, key: KeyEvent) {
         }
     }
 }
+
+fn link_marked(current_state: &ExplorerState, other_state: &mut ExplorerState, kind: LinkKind) {
+    let destination = other_state
+        .selected
+        .clone()
+        .unwrap_or_else(|| other_state.root.directory.path.clone());
+
+    current_state.link_marked(&destination, kind);
+
+    let directory = match destination.is_file() {
+        true => destination.parent().map(Path::to_path_buf),
+        false => Some(destination),
+    };
+    other_state.sync_directories(directory);
+}
@@ -1,2 +1,10 @@
+pub mod analyzer;
+pub mod bookmarks;
+pub mod diff;
 pub mod directory;
+pub mod duplicates;
 pub mod input_handler;
+pub mod permissions;
+pub mod prompt;
+pub mod sync;
+pub mod visual;
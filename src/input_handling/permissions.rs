@@ -0,0 +1,63 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use log::error;
+
+use crate::{
+    app::App,
+    file_management::permissions,
+    state::diode::{attribute_state::AttributeChange, permissions_state::PermissionsState},
+};
+
+pub fn open(app: &mut App) {
+    let current_state = app.diode_state.get_current_state();
+    let targets = current_state.get_marked_or_selected();
+    let Some(entry) = targets.first().and_then(|v| current_state.entries.get(v)) else {
+        return;
+    };
+    let mode = permissions::get_mode(entry.metadata());
+    app.diode_state.permissions = Some(PermissionsState::new(targets, mode));
+}
+
+pub fn on_key_event(app: &mut App, key: KeyEvent) {
+    let Some(permissions) = app.diode_state.permissions.as_mut() else {
+        return;
+    };
+
+    match key.code {
+        KeyCode::Esc => app.diode_state.permissions = None,
+        KeyCode::Enter => {
+            if let Some(permissions) = app.diode_state.permissions.take() {
+                apply(app, permissions);
+            }
+        }
+        KeyCode::Up => permissions.move_cursor(-1, 0),
+        KeyCode::Down => permissions.move_cursor(1, 0),
+        KeyCode::Left => permissions.move_cursor(0, -1),
+        KeyCode::Right => permissions.move_cursor(0, 1),
+        KeyCode::Char(' ') => permissions.toggle(),
+        KeyCode::Tab => permissions.cycle_recursion(),
+        KeyCode::Backspace => {
+            permissions.input.pop();
+        }
+        KeyCode::Char(c) => permissions.input.push(c),
+        _ => {}
+    }
+}
+
+fn apply(app: &mut App, permissions: PermissionsState) {
+    let change = match permissions.get_change() {
+        Ok(v) => v,
+        Err(e) => {
+            error!("Invalid mode {:?}: {}", permissions.input, e);
+            return;
+        }
+    };
+
+    let change = AttributeChange::Mode {
+        change,
+        recursion: permissions.recursion,
+    };
+    let pane = app.diode_state.selected;
+    app.diode_state
+        .attributes
+        .request(pane, permissions.targets, change);
+}
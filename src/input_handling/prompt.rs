@@ -0,0 +1,215 @@
+use std::{fs, io, path::Path, time::SystemTime};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use log::error;
+
+use crate::{
+    app::App,
+    file_management::{
+        archive::ArchiveFormat,
+        ownership::OwnerChange,
+        path,
+        pattern::Pattern,
+        times::{self, TouchSource},
+        xattrs,
+    },
+    input_handling::{analyzer, duplicates},
+    state::diode::{
+        attribute_state::AttributeChange,
+        diode_state::{DiodeState, Selection},
+        explorer_state::ExplorerState,
+        prompt_state::{PromptKind, PromptState},
+    },
+};
+
+pub fn on_key_event(app: &mut App, key: KeyEvent) {
+    let base = app
+        .diode_state
+        .get_current_state()
+        .root
+        .directory
+        .path
+        .clone();
+    let Some(prompt) = app.diode_state.prompt.as_mut() else {
+        return;
+    };
+
+    match key.code {
+        KeyCode::Esc => app.diode_state.prompt = None,
+        KeyCode::Enter => {
+            if let Some(prompt) = app.diode_state.prompt.take() {
+                submit(app, prompt);
+            }
+        }
+        KeyCode::Backspace => {
+            prompt.input.pop();
+            prompt.completions.clear();
+        }
+        KeyCode::Tab => match &mut prompt.kind {
+            PromptKind::Mark { syntax, .. } => *syntax = syntax.toggle(),
+            PromptKind::Ownership { recursive } => *recursive = !*recursive,
+            PromptKind::Touch { source } => *source = source.next(),
+            PromptKind::Bookmark
+            | PromptKind::Jump
+            | PromptKind::Xattr { .. }
+            | PromptKind::Delete { .. }
+            | PromptKind::Archive => {}
+            PromptKind::GoTo => {
+                let completions = path::complete(&prompt.input, &base);
+                if let Some(prefix) = path::common_prefix(&completions) {
+                    prompt.input = prefix;
+                }
+                prompt.completions = completions;
+            }
+        },
+        KeyCode::Char(c) => {
+            prompt.input.push(c);
+            prompt.completions.clear();
+        }
+        _ => {}
+    }
+    update_jump_target(&mut app.diode_state);
+}
+
+/// Finding the target checks candidates on disk, so it is done once per
+/// input change rather than on every draw.
+pub fn update_jump_target(diode_state: &mut DiodeState) {
+    let Some(prompt) = &mut diode_state.prompt else {
+        return;
+    };
+    if prompt.kind == PromptKind::Jump {
+        prompt.jump_target = diode_state
+            .frecency
+            .find(&prompt.input)
+            .map(Path::to_path_buf);
+    }
+}
+
+fn submit(app: &mut App, prompt: PromptState) {
+    let diode_state = &mut app.diode_state;
+    let current_state = match diode_state.selected {
+        Selection::Left => &mut diode_state.left_state,
+        Selection::Right => &mut diode_state.right_state,
+    };
+    match prompt.kind {
+        PromptKind::Bookmark if prompt.input.is_empty() => {}
+        PromptKind::Bookmark => {
+            let path = current_state.root.directory.path.clone();
+            diode_state.bookmarks.set(prompt.input, path);
+        }
+        PromptKind::Jump => {
+            diode_state.frecency.prune();
+            let Some(target) = diode_state.frecency.find(&prompt.input) else {
+                return;
+            };
+            if let Err(e) = current_state.jump_to(target.to_path_buf()) {
+                error!("Failed to jump to {:?}: {}", target, e);
+            }
+        }
+        PromptKind::GoTo => {
+            let result = path::resolve(&prompt.input, &current_state.root.directory.path)
+                .and_then(|v| current_state.reveal(v));
+            if let Err(e) = result {
+                error!("Failed to go to {:?}: {}", prompt.input, e);
+            }
+        }
+        PromptKind::Ownership { recursive } => match OwnerChange::parse(&prompt.input) {
+            Ok(change) => {
+                let targets = current_state.get_marked_or_selected();
+                let change = AttributeChange::Owner { change, recursive };
+                let pane = diode_state.selected;
+                diode_state.attributes.request(pane, targets, change);
+            }
+            Err(e) => error!("Invalid owner {:?}: {}", prompt.input, e),
+        },
+        PromptKind::Delete { .. } if prompt.input.trim() == "y" => {
+            match diode_state.duplicates.is_some() {
+                true => duplicates::delete_marked(diode_state),
+                false => analyzer::delete_marked(current_state),
+            }
+        }
+        PromptKind::Delete { .. } => {}
+        PromptKind::Archive => archive(diode_state, prompt.input.trim()),
+        PromptKind::Xattr { remove } => change_xattr(current_state, &prompt.input, remove),
+        PromptKind::Touch { source } => touch(diode_state, source, &prompt.input),
+        PromptKind::Mark { marked, syntax } => match Pattern::new(&prompt.input, syntax) {
+            Ok(pattern) => current_state.set_marked_matching(&pattern, marked),
+            Err(e) => error!("Invalid pattern {:?}: {}", prompt.input, e),
+        },
+    }
+}
+
+fn touch(diode_state: &mut DiodeState, source: TouchSource, input: &str) {
+    let time = match source {
+        TouchSource::Now => Some(SystemTime::now()),
+        TouchSource::Date => match times::parse(input) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                error!("Invalid date {:?}: {}", input, e);
+                return;
+            }
+        },
+        TouchSource::Other => None,
+    };
+
+    let (current_state, other_state) = diode_state.get_states_mut();
+    let current_root = current_state.root.directory.path.clone();
+    let other_root = &other_state.root.directory.path;
+    for target in current_state.get_marked_or_selected() {
+        let result = match time {
+            Some(time) => times::set_times(&target, time, time),
+            None => path::rebase(&target, &current_root, other_root)
+                .ok_or_else(|| io::Error::other("Entry is outside of the pane root"))
+                .and_then(fs::metadata)
+                .and_then(|v| times::get_times(&v))
+                .and_then(|(accessed, modified)| times::set_times(&target, accessed, modified)),
+        };
+        if let Err(e) = result {
+            error!("Failed to set times of {:?}: {}", target, e);
+        }
+        current_state.update_metadata(&target);
+    }
+}
+
+fn change_xattr(state: &mut ExplorerState, input: &str, remove: bool) {
+    let (name, value) = input.split_once('=').unwrap_or((input, ""));
+    let name = name.trim();
+    if name.is_empty() {
+        return;
+    }
+
+    for target in state.get_marked_or_selected() {
+        let result = match remove {
+            true => xattrs::remove(&target, name),
+            false => xattrs::set(&target, name, value.as_bytes()),
+        };
+        if let Err(e) = result {
+            error!(
+                "Failed to change attribute {:?} of {:?}: {}",
+                name, target, e
+            );
+        }
+        // Rereads the details, which cache the attributes.
+        state.update_metadata(&target);
+    }
+}
+
+/// Queues an archive of the marked entries in the other pane's root.
+fn archive(diode_state: &mut DiodeState, name: &str) {
+    if name.is_empty() {
+        return;
+    }
+    let Some(format) = ArchiveFormat::from_name(name) else {
+        error!("Unknown archive format for {:?}", name);
+        return;
+    };
+
+    let current_state = diode_state.get_current_state();
+    let entries = current_state.get_marked_or_selected();
+    if entries.is_empty() {
+        return;
+    }
+    let root = current_state.root.directory.path.clone();
+    let target = diode_state.get_other_state().root.directory.path.join(name);
+    diode_state.archives.request(format, target, root, entries);
+}
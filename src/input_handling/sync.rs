@@ -0,0 +1,24 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{app::App, state::diode::sync_state::SyncStage};
+
+pub fn on_key_event(app: &mut App, key: KeyEvent) {
+    let Some(sync) = app.diode_state.sync.as_mut() else {
+        return;
+    };
+
+    match (key.modifiers, key.code) {
+        (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => app.quit(),
+        // Closing the dialog also cancels a running sync.
+        (_, KeyCode::Esc | KeyCode::Char('q')) => app.diode_state.sync = None,
+        (_, KeyCode::Tab) => sync.cycle_mode(),
+        (_, KeyCode::Char('j') | KeyCode::Down) => sync.move_by(1),
+        (_, KeyCode::Char('k') | KeyCode::Up) => sync.move_by(-1),
+        (_, KeyCode::Enter) => match sync.stage {
+            SyncStage::Ready => sync.run(),
+            SyncStage::Finished(_) | SyncStage::Failed(_) => app.diode_state.sync = None,
+            _ => {}
+        },
+        _ => {}
+    }
+}
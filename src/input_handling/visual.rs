@@ -0,0 +1,13 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::state::diode::explorer_state::ExplorerState;
+
+pub fn on_key_event(key: KeyEvent, state: &mut ExplorerState) {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => state.move_down(),
+        KeyCode::Char('k') | KeyCode::Up => state.move_up(),
+        KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('V') => state.apply_visual(),
+        KeyCode::Esc => state.cancel_visual(),
+        _ => {}
+    }
+}
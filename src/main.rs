@@ -1,4 +1,5 @@
 mod app;
+mod event;
 mod file_management;
 mod input_handling;
 mod state;
@@ -7,7 +8,8 @@ mod ui;
 use crate::{
     file_management::directory::Directory,
     state::diode::{
-        diode_state::DiodeState, directory_state::DirectoryState, explorer_state::ExplorerState,
+        bookmark_state::BookmarkState, diode_state::DiodeState, directory_state::DirectoryState,
+        explorer_state::ExplorerState, frecency_state::FrecencyState,
     },
 };
 use log::{LevelFilter, debug};
@@ -29,14 +31,19 @@ async fn main() -> color_eyre::Result<()> {
     debug!("Starting diode");
 
     let current_dir = env::current_dir()?;
-    let left_explorer = ExplorerState::try_new(DirectoryState::from(Directory::try_from(
+    let left_explorer = ExplorerState::new(DirectoryState::from(Directory::try_from(
         current_dir.clone(),
-    )?))?;
-    let right_explorer = ExplorerState::try_new(DirectoryState::from(Directory::try_from(
+    )?));
+    let right_explorer = ExplorerState::new(DirectoryState::from(Directory::try_from(
         current_dir.clone(),
-    )?))?;
+    )?));
     let terminal = ratatui::init();
-    let diode_state = DiodeState::new(left_explorer, right_explorer);
+    let diode_state = DiodeState::new(
+        left_explorer,
+        right_explorer,
+        BookmarkState::load(),
+        FrecencyState::load(),
+    );
     let result = App::new(diode_state).run(terminal).await;
     ratatui::restore();
     result
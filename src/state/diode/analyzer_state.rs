@@ -0,0 +1,160 @@
+use std::{
+    collections::BTreeSet,
+    io,
+    path::{Path, PathBuf},
+};
+
+use log::error;
+use ratatui::widgets::ListState;
+
+use crate::file_management::usage::UsageNode;
+
+/// Disk usage view of a pane, drilling into the tree scanned from `root`.
+#[derive(Debug)]
+pub struct AnalyzerState {
+    /// Identifies the latest scan, whose results are the only ones accepted.
+    pub id: u64,
+    pub root: PathBuf,
+    pub tree: Option<UsageNode>,
+    /// Relative to `root`.
+    pub current: PathBuf,
+    pub marked: BTreeSet<PathBuf>,
+    pub list_state: ListState,
+    scan_requested: bool,
+}
+
+impl AnalyzerState {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            id: 0,
+            root,
+            tree: None,
+            current: PathBuf::new(),
+            marked: BTreeSet::new(),
+            list_state: ListState::default(),
+            scan_requested: true,
+        }
+    }
+
+    pub fn take_scan_request(&mut self) -> Option<PathBuf> {
+        match std::mem::take(&mut self.scan_requested) {
+            true => Some(self.root.clone()),
+            false => None,
+        }
+    }
+
+    pub fn rescan(&mut self) {
+        self.tree = None;
+        self.scan_requested = true;
+    }
+
+    pub fn on_scanned(&mut self, id: u64, root: &Path, result: io::Result<UsageNode>) {
+        if id != self.id || self.scan_requested {
+            return;
+        }
+
+        match result {
+            Ok(v) => {
+                self.tree = Some(v);
+                // The previous location may be gone after a rescan.
+                if self.get_current().is_none() {
+                    self.current = PathBuf::new();
+                }
+                self.select(0);
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => error!("Failed to analyze {:?}: {}", root, e),
+        }
+    }
+
+    pub fn get_current(&self) -> Option<&UsageNode> {
+        self.tree.as_ref()?.find(&self.current)
+    }
+
+    pub fn get_current_path(&self) -> PathBuf {
+        match self.current.as_os_str().is_empty() {
+            true => self.root.clone(),
+            false => self.root.join(&self.current),
+        }
+    }
+
+    pub fn get_selected(&self) -> Option<&UsageNode> {
+        self.get_current()?
+            .children
+            .get(self.list_state.selected()?)
+    }
+
+    pub fn get_selected_path(&self) -> Option<PathBuf> {
+        let selected = self.get_selected()?;
+        Some(self.get_current_path().join(&selected.name))
+    }
+
+    fn select(&mut self, index: usize) {
+        let len = self.get_current().map_or(0, |v| v.children.len());
+        let index = match len {
+            0 => None,
+            _ => Some(index.min(len - 1)),
+        };
+        self.list_state.select(index);
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let index = self.list_state.selected().unwrap_or_default();
+        self.select(index.saturating_add_signed(delta));
+    }
+
+    pub fn enter(&mut self) {
+        let Some(selected) = self.get_selected().filter(|v| v.is_dir) else {
+            return;
+        };
+        self.current = self.current.join(&selected.name);
+        *self.list_state.offset_mut() = 0;
+        self.select(0);
+    }
+
+    /// Goes up one level and selects the directory that was left.
+    pub fn leave(&mut self) {
+        let Some(name) = self.current.file_name().map(|v| v.to_owned()) else {
+            return;
+        };
+        self.current.pop();
+        let index = self
+            .get_current()
+            .and_then(|v| v.children.iter().position(|v| v.name == name))
+            .unwrap_or_default();
+        self.select(index);
+    }
+
+    pub fn toggle_marked(&mut self) {
+        let Some(path) = self.get_selected_path() else {
+            return;
+        };
+        if !self.marked.remove(&path) {
+            self.marked.insert(path);
+        }
+        self.move_by(1);
+    }
+
+    /// The marked paths that are not inside another marked directory.
+    pub fn get_marked_roots(&self) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for path in &self.marked {
+            if !roots.iter().any(|v| path.starts_with(v)) {
+                roots.push(path.clone());
+            }
+        }
+        roots
+    }
+
+    /// Drops an entry that was deleted or moved away from the tree.
+    pub fn remove(&mut self, path: &Path) {
+        self.marked.retain(|v| !v.starts_with(path));
+        if let Some(tree) = &mut self.tree
+            && let Ok(relative) = path.strip_prefix(&self.root)
+        {
+            tree.remove(relative);
+        }
+        let index = self.list_state.selected().unwrap_or_default();
+        self.select(index);
+    }
+}
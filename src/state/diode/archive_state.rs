@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use crate::file_management::archive::ArchiveFormat;
+
+#[derive(Debug)]
+pub struct ArchiveRequest {
+    pub id: u64,
+    pub format: ArchiveFormat,
+    pub target: PathBuf,
+    /// Entry names in the archive are relative to this.
+    pub root: PathBuf,
+    pub entries: Vec<PathBuf>,
+}
+
+#[derive(Debug)]
+pub struct ArchiveJob {
+    pub id: u64,
+    pub target: PathBuf,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Archives being written in the background.
+#[derive(Debug, Default)]
+pub struct ArchiveState {
+    pub jobs: Vec<ArchiveJob>,
+    requests: Vec<ArchiveRequest>,
+    next_id: u64,
+}
+
+impl ArchiveState {
+    pub fn request(
+        &mut self,
+        format: ArchiveFormat,
+        target: PathBuf,
+        root: PathBuf,
+        entries: Vec<PathBuf>,
+    ) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.jobs.push(ArchiveJob {
+            id,
+            target: target.clone(),
+            done: 0,
+            total: 0,
+        });
+        self.requests.push(ArchiveRequest {
+            id,
+            format,
+            target,
+            root,
+            entries,
+        });
+    }
+
+    pub fn take_requests(&mut self) -> Vec<ArchiveRequest> {
+        std::mem::take(&mut self.requests)
+    }
+
+    pub fn on_progress(&mut self, id: u64, done: usize, total: usize) {
+        if let Some(job) = self.jobs.iter_mut().find(|v| v.id == id) {
+            job.done = done;
+            job.total = total;
+        }
+    }
+
+    pub fn finish(&mut self, id: u64) -> Option<ArchiveJob> {
+        let index = self.jobs.iter().position(|v| v.id == id)?;
+        Some(self.jobs.remove(index))
+    }
+}
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use crate::{
+    file_management::{ownership::OwnerChange, permissions::ModeChange, walk::RecurseTarget},
+    state::diode::diode_state::Selection,
+};
+
+/// What is changed on each target, and whether it recurses into directories.
+#[derive(Debug, Clone)]
+pub enum AttributeChange {
+    Mode {
+        change: ModeChange,
+        recursion: Option<RecurseTarget>,
+    },
+    Owner {
+        change: OwnerChange,
+        recursive: bool,
+    },
+}
+
+#[derive(Debug)]
+pub struct AttributeRequest {
+    /// The pane whose directories are reloaded afterwards.
+    pub pane: Selection,
+    pub targets: Vec<PathBuf>,
+    pub change: AttributeChange,
+}
+
+/// Mode and owner changes waiting to run in the background, as they may walk whole trees.
+#[derive(Debug, Default)]
+pub struct AttributeState {
+    requests: Vec<AttributeRequest>,
+}
+
+impl AttributeState {
+    pub fn request(&mut self, pane: Selection, targets: Vec<PathBuf>, change: AttributeChange) {
+        self.requests.push(AttributeRequest {
+            pane,
+            targets,
+            change,
+        });
+    }
+
+    pub fn take_requests(&mut self) -> Vec<AttributeRequest> {
+        std::mem::take(&mut self.requests)
+    }
+}
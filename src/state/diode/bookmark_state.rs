@@ -0,0 +1,91 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use log::error;
+
+use crate::file_management::{bookmarks, data};
+
+#[derive(Debug, Default)]
+pub struct BookmarkState {
+    pub bookmarks: BTreeMap<String, PathBuf>,
+    pub picker: Option<usize>,
+    file: Option<PathBuf>,
+}
+
+impl BookmarkState {
+    pub fn load() -> Self {
+        let file = match data::data_file("bookmarks") {
+            Ok(v) => v,
+            Err(e) => {
+                error!("Bookmarks will not be persisted: {}", e);
+                return Self::default();
+            }
+        };
+
+        let bookmarks = bookmarks::load(&file).unwrap_or_else(|e| {
+            error!("Failed to load bookmarks from {:?}: {}", file, e);
+            BTreeMap::new()
+        });
+
+        Self {
+            bookmarks,
+            picker: None,
+            file: Some(file),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PathBuf> {
+        self.bookmarks.get(name)
+    }
+
+    pub fn set(&mut self, name: String, path: PathBuf) {
+        // The bookmarks file is text, other paths would not read back the same.
+        if path.to_str().is_none() {
+            error!("Cannot bookmark {:?}: the path is not valid UTF-8", path);
+            return;
+        }
+        self.bookmarks.insert(name, path);
+        self.save();
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.bookmarks.remove(name);
+        self.save();
+        if let Some(index) = &mut self.picker {
+            *index = (*index).min(self.bookmarks.len().saturating_sub(1));
+        }
+    }
+
+    pub fn open_picker(&mut self) {
+        self.picker = Some(0);
+    }
+
+    pub fn close_picker(&mut self) {
+        self.picker = None;
+    }
+
+    pub fn picker_next(&mut self) {
+        if let Some(index) = &mut self.picker {
+            *index = (*index + 1).min(self.bookmarks.len().saturating_sub(1));
+        }
+    }
+
+    pub fn picker_previous(&mut self) {
+        if let Some(index) = &mut self.picker {
+            *index = index.saturating_sub(1);
+        }
+    }
+
+    pub fn get_picked(&self) -> Option<(&String, &PathBuf)> {
+        self.bookmarks.iter().nth(self.picker?)
+    }
+
+    fn save(&self) {
+        let Some(file) = &self.file else {
+            return;
+        };
+
+        if let Err(e) = bookmarks::save(file, &self.bookmarks) {
+            error!("Failed to save bookmarks to {:?}: {}", file, e);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::{
+    file_management::compare::{self, Comparison, TreePair},
+    state::diode::{diode_state::Selection, explorer_state::ExplorerState},
+};
+
+/// Files of equal size whose content still has to be compared.
+#[derive(Debug, Clone)]
+pub struct HashPair {
+    pub left: PathBuf,
+    pub right: PathBuf,
+    /// The result to use when the content differs.
+    pub differs: Comparison,
+}
+
+/// Result of comparing everything below both pane roots.
+#[derive(Debug, Default)]
+pub struct CompareState {
+    pub id: u64,
+    /// Stops the walk and the content comparisons of this compare.
+    pub cancelled: Arc<AtomicBool>,
+    left: HashMap<PathBuf, Comparison>,
+    right: HashMap<PathBuf, Comparison>,
+    requests: Vec<HashPair>,
+    walk_request: Option<(PathBuf, PathBuf)>,
+    hash: bool,
+    pub walking: bool,
+    pub hashing: usize,
+}
+
+impl CompareState {
+    /// Queues walking both roots, the result arrives through `on_walked`.
+    pub fn new(id: u64, left_root: PathBuf, right_root: PathBuf, hash: bool) -> Self {
+        Self {
+            id,
+            walk_request: Some((left_root, right_root)),
+            hash,
+            walking: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn take_walk_request(&mut self) -> Option<(PathBuf, PathBuf)> {
+        self.walk_request.take()
+    }
+
+    /// Classifies the paired entries. Directories present on both sides are
+    /// not classified themselves.
+    pub fn on_walked(&mut self, pairs: Vec<TreePair>) {
+        self.walking = false;
+        for pair in pairs {
+            let ((left, left_metadata), (right, right_metadata)) = match (pair.left, pair.right) {
+                (Some(left), Some(right)) => (left, right),
+                (Some((left, _)), None) => {
+                    self.left.insert(left, Comparison::OnlyLeft);
+                    continue;
+                }
+                (None, Some((right, _))) => {
+                    self.right.insert(right, Comparison::OnlyRight);
+                    continue;
+                }
+                (None, None) => continue,
+            };
+            if left_metadata.is_dir() && right_metadata.is_dir() {
+                continue;
+            }
+
+            self.set(
+                &left,
+                &right,
+                compare::compare(&left_metadata, &right_metadata),
+            );
+            if self.hash
+                && left_metadata.is_file()
+                && right_metadata.is_file()
+                && left_metadata.len() == right_metadata.len()
+            {
+                self.requests.push(HashPair {
+                    differs: compare::compare_modified(&left_metadata, &right_metadata),
+                    left,
+                    right,
+                });
+            }
+        }
+        self.hashing = self.requests.len();
+    }
+
+    fn set(&mut self, left: &Path, right: &Path, comparison: Comparison) {
+        self.left.insert(left.to_path_buf(), comparison);
+        self.right.insert(right.to_path_buf(), comparison.flip());
+    }
+
+    pub fn get(&self, pane: Selection) -> &HashMap<PathBuf, Comparison> {
+        match pane {
+            Selection::Left => &self.left,
+            Selection::Right => &self.right,
+        }
+    }
+
+    pub fn take_requests(&mut self) -> Vec<HashPair> {
+        std::mem::take(&mut self.requests)
+    }
+
+    pub fn on_hashed(&mut self, pair: &HashPair, equal: bool) {
+        let comparison = match equal {
+            true => Comparison::Identical,
+            false => pair.differs,
+        };
+        self.set(&pair.left, &pair.right, comparison);
+        self.hashing = self.hashing.saturating_sub(1);
+    }
+
+    /// Marks the differences in `state` and unmarks everything else.
+    pub fn apply_marks(&self, pane: Selection, state: &mut ExplorerState) {
+        state.clear_marked();
+        self.mark_differences(pane, state, state.entries.keys().cloned().collect());
+    }
+
+    /// Marks the differences among `paths`, used for entries loaded later on.
+    pub fn mark_differences(
+        &self,
+        pane: Selection,
+        state: &mut ExplorerState,
+        paths: Vec<PathBuf>,
+    ) {
+        let comparisons = self.get(pane);
+        for path in paths {
+            if comparisons.get(&path).is_some_and(|v| v.is_difference()) {
+                state.set_marked(&path, true);
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use crate::file_management::diff::FileDiff;
+
+/// Line diff between the left and the right file, scrolled by rows. The diff
+/// is computed in the background and `None` until it arrives.
+#[derive(Debug)]
+pub struct DiffState {
+    /// Identifies the diff job, whose result is the only one accepted.
+    pub id: u64,
+    pub left: PathBuf,
+    pub right: PathBuf,
+    pub diff: Option<FileDiff>,
+    /// The row each hunk header is drawn at in the current layout.
+    pub hunk_rows: Vec<usize>,
+    /// Rows taken by all hunks in the current layout.
+    pub total_rows: usize,
+    requested: bool,
+    pub side_by_side: bool,
+    pub scroll: usize,
+    pub height: usize,
+}
+
+impl DiffState {
+    pub fn new(left: PathBuf, right: PathBuf) -> Self {
+        Self {
+            id: 0,
+            left,
+            right,
+            diff: None,
+            hunk_rows: Vec::new(),
+            total_rows: 0,
+            requested: true,
+            side_by_side: false,
+            scroll: 0,
+            height: 0,
+        }
+    }
+
+    pub fn take_request(&mut self) -> Option<(PathBuf, PathBuf)> {
+        match std::mem::take(&mut self.requested) {
+            true => Some((self.left.clone(), self.right.clone())),
+            false => None,
+        }
+    }
+
+    pub fn on_loaded(&mut self, diff: FileDiff) {
+        self.diff = Some(diff);
+        self.update_rows();
+    }
+
+    /// Lays out the hunks, which only changes when the diff arrives or the
+    /// layout is toggled.
+    fn update_rows(&mut self) {
+        self.hunk_rows.clear();
+        self.total_rows = 0;
+        let Some(FileDiff::Text(hunks)) = &self.diff else {
+            return;
+        };
+        for hunk in hunks {
+            self.hunk_rows.push(self.total_rows);
+            self.total_rows += match self.side_by_side {
+                true => 1 + hunk.pair_lines().len(),
+                false => 1 + hunk.lines.len(),
+            };
+        }
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        let last = self.total_rows.saturating_sub(self.height);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn scroll_half_page(&mut self, down: bool) {
+        let delta = (self.height / 2).max(1) as isize;
+        self.scroll_by(if down { delta } else { -delta });
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll = 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_by(isize::MAX);
+    }
+
+    pub fn next_hunk(&mut self) {
+        if let Some(row) = self.hunk_rows.iter().find(|v| **v > self.scroll) {
+            self.scroll = *row;
+        }
+    }
+
+    pub fn previous_hunk(&mut self) {
+        if let Some(row) = self.hunk_rows.iter().rfind(|v| **v < self.scroll) {
+            self.scroll = *row;
+        }
+    }
+
+    /// Switches between unified and side by side, staying at the same hunk.
+    pub fn toggle_layout(&mut self) {
+        let hunk = self
+            .hunk_rows
+            .iter()
+            .rposition(|v| *v <= self.scroll)
+            .unwrap_or_default();
+        self.side_by_side = !self.side_by_side;
+        self.update_rows();
+        self.scroll = self.hunk_rows.get(hunk).copied().unwrap_or_default();
+    }
+}
@@ -1,24 +1,78 @@
-use crate::state::diode::explorer_state::ExplorerState;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use log::error;
+
+use crate::{
+    file_management::{compare::TreePair, entry::Entry},
+    state::diode::{
+        archive_state::ArchiveState,
+        attribute_state::AttributeState,
+        bookmark_state::BookmarkState,
+        compare_state::{CompareState, HashPair},
+        diff_state::DiffState,
+        duplicates_state::DuplicatesState,
+        explorer_state::ExplorerState,
+        frecency_state::FrecencyState,
+        permissions_state::PermissionsState,
+        prompt_state::PromptState,
+        sync_state::SyncState,
+        usage_state::UsageState,
+    },
+};
 
 #[derive(Debug)]
 pub struct DiodeState {
     pub left_state: ExplorerState,
     pub right_state: ExplorerState,
     pub selected: Selection,
+    pub prompt: Option<PromptState>,
+    pub permissions: Option<PermissionsState>,
+    pub bookmarks: BookmarkState,
+    pub frecency: FrecencyState,
+    pub usage: UsageState,
+    pub compare: Option<CompareState>,
+    compare_id: u64,
+    pub sync: Option<SyncState>,
+    pub diff: Option<DiffState>,
+    pub duplicates: Option<DuplicatesState>,
+    pub archives: ArchiveState,
+    pub attributes: AttributeState,
+    pub pending_key: Option<char>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Selection {
     Left,
     Right,
 }
 
 impl DiodeState {
-    pub fn new(left_state: ExplorerState, right_state: ExplorerState) -> Self {
+    pub fn new(
+        left_state: ExplorerState,
+        right_state: ExplorerState,
+        bookmarks: BookmarkState,
+        frecency: FrecencyState,
+    ) -> Self {
         Self {
             left_state,
             right_state,
             selected: Selection::Left,
+            prompt: None,
+            permissions: None,
+            bookmarks,
+            frecency,
+            usage: UsageState::default(),
+            compare: None,
+            compare_id: 0,
+            sync: None,
+            diff: None,
+            duplicates: None,
+            archives: ArchiveState::default(),
+            attributes: AttributeState::default(),
+            pending_key: None,
         }
     }
 
@@ -29,6 +83,133 @@ impl DiodeState {
         };
     }
 
+    pub fn record_visits(&mut self) {
+        self.frecency.record_roots(&[
+            &self.left_state.root.directory.path,
+            &self.right_state.root.directory.path,
+        ]);
+    }
+
+    /// Compares both roots and marks the differences, or leaves compare mode.
+    pub fn toggle_compare(&mut self, hash: bool) {
+        let id = match self.close_compare() {
+            true => return,
+            false => self.compare_id,
+        };
+        self.compare_id += 1;
+
+        self.compare = Some(CompareState::new(
+            id,
+            self.left_state.root.directory.path.clone(),
+            self.right_state.root.directory.path.clone(),
+            hash,
+        ));
+    }
+
+    /// Leaves compare mode and stops its jobs, returning whether it was on.
+    pub fn close_compare(&mut self) -> bool {
+        match self.compare.take() {
+            Some(compare) => {
+                compare.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn on_compared(&mut self, id: u64, result: io::Result<Vec<TreePair>>) {
+        let Some(compare) = self.compare.as_mut().filter(|v| v.id == id) else {
+            return;
+        };
+        match result {
+            Ok(pairs) => compare.on_walked(pairs),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::Interrupted {
+                    error!("Failed to compare the pane roots: {}", e);
+                }
+                self.compare = None;
+                return;
+            }
+        }
+        compare.apply_marks(Selection::Left, &mut self.left_state);
+        compare.apply_marks(Selection::Right, &mut self.right_state);
+    }
+
+    /// Adds loaded entries to `pane`, marking them when they differ from
+    /// their counterpart in compare mode.
+    pub fn on_entries_loaded(
+        &mut self,
+        pane: Selection,
+        id: u64,
+        directory: &Path,
+        entries: Vec<Entry>,
+    ) {
+        let state = self.get_state_mut(pane);
+        let added: Vec<PathBuf> = entries
+            .iter()
+            .map(|v| match v {
+                Entry::Directory(v) => &v.path,
+                Entry::File(v) => &v.path,
+            })
+            .filter(|v| !state.entries.contains_key(*v))
+            .cloned()
+            .collect();
+        state.on_entries_loaded(id, directory, entries);
+
+        if let Some(compare) = &self.compare {
+            let state = match pane {
+                Selection::Left => &mut self.left_state,
+                Selection::Right => &mut self.right_state,
+            };
+            compare.mark_differences(pane, state, added);
+        }
+    }
+
+    pub fn on_hashed(&mut self, id: u64, pair: HashPair, equal: bool) {
+        let Some(compare) = self.compare.as_mut().filter(|v| v.id == id) else {
+            return;
+        };
+        compare.on_hashed(&pair, equal);
+        self.left_state.set_marked(&pair.left, !equal);
+        self.right_state.set_marked(&pair.right, !equal);
+    }
+
+    /// Plans syncing the other root with the current one.
+    pub fn open_sync(&mut self) {
+        let source = self.get_current_state().root.directory.path.clone();
+        let destination = self.get_other_state().root.directory.path.clone();
+        self.sync = Some(SyncState::new(source, destination));
+    }
+
+    pub fn open_duplicates(&mut self) {
+        self.duplicates = Some(DuplicatesState::new(
+            self.get_current_state().root.directory.path.clone(),
+            self.get_other_state().root.directory.path.clone(),
+            self.left_state.root.directory.path.clone(),
+        ));
+    }
+
+    pub fn get_current_state(&self) -> &ExplorerState {
+        match self.selected {
+            Selection::Left => &self.left_state,
+            Selection::Right => &self.right_state,
+        }
+    }
+
+    pub fn get_other_state(&self) -> &ExplorerState {
+        match self.selected {
+            Selection::Left => &self.right_state,
+            Selection::Right => &self.left_state,
+        }
+    }
+
+    pub fn get_state_mut(&mut self, pane: Selection) -> &mut ExplorerState {
+        match pane {
+            Selection::Left => &mut self.left_state,
+            Selection::Right => &mut self.right_state,
+        }
+    }
+
     pub fn get_states_mut(&mut self) -> (&mut ExplorerState, &mut ExplorerState) {
         match self.selected {
             Selection::Left => (&mut self.left_state, &mut self.right_state),
@@ -1,6 +1,4 @@
-use std::io;
-
-use crate::{file_management::directory::Directory, state::diode::entry_state::EntryState};
+use crate::{file_management::directory::Directory, state::diode::entry_state::EntryDetails};
 
 #[derive(Debug, Clone)]
 pub struct DirectoryState {
@@ -8,6 +6,7 @@ pub struct DirectoryState {
     pub collapsed: bool,
     pub selected: bool,
     pub marked: bool,
+    pub details: Option<EntryDetails>,
 }
 
 impl DirectoryState {
@@ -17,17 +16,9 @@ impl DirectoryState {
             collapsed: true,
             selected: false,
             marked: false,
+            details: None,
         }
     }
-
-    pub fn load_entry_states(&self) -> io::Result<Vec<EntryState>> {
-        Ok(self
-            .directory
-            .load_entries()?
-            .into_iter()
-            .map(|v| v.into())
-            .collect())
-    }
 }
 
 impl From<Directory> for DirectoryState {
@@ -0,0 +1,212 @@
+use std::{
+    collections::BTreeSet,
+    io,
+    path::{Path, PathBuf},
+};
+
+use log::error;
+use ratatui::widgets::ListState;
+
+use crate::file_management::duplicates::{DuplicateAction, DuplicateGroup, KeepRule};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicatesJob {
+    Scan,
+    Resolve(DuplicateAction),
+}
+
+/// Progress of deleting or linking the marked files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DuplicatesProgress {
+    pub action: DuplicateAction,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Groups of equal files below the current pane root, or both pane roots.
+/// Each group is shown as a header row followed by a row per file.
+#[derive(Debug)]
+pub struct DuplicatesState {
+    /// Identifies the running job, results of older jobs are dropped.
+    pub id: u64,
+    pub current_root: PathBuf,
+    pub other_root: PathBuf,
+    pub left_root: PathBuf,
+    pub both: bool,
+    pub groups: Option<Vec<DuplicateGroup>>,
+    pub marked: BTreeSet<PathBuf>,
+    pub list_state: ListState,
+    pub progress: Option<DuplicatesProgress>,
+    request: Option<DuplicatesJob>,
+}
+
+impl DuplicatesState {
+    pub fn new(current_root: PathBuf, other_root: PathBuf, left_root: PathBuf) -> Self {
+        Self {
+            id: 0,
+            current_root,
+            other_root,
+            left_root,
+            both: false,
+            groups: None,
+            marked: BTreeSet::new(),
+            list_state: ListState::default(),
+            progress: None,
+            request: Some(DuplicatesJob::Scan),
+        }
+    }
+
+    pub fn get_roots(&self) -> Vec<PathBuf> {
+        match self.both && self.other_root != self.current_root {
+            true => vec![self.current_root.clone(), self.other_root.clone()],
+            false => vec![self.current_root.clone()],
+        }
+    }
+
+    pub fn take_request(&mut self) -> Option<DuplicatesJob> {
+        self.request.take()
+    }
+
+    /// Scans again, which also stops deleting or linking.
+    pub fn rescan(&mut self) {
+        self.groups = None;
+        self.marked.clear();
+        self.progress = None;
+        self.request = Some(DuplicatesJob::Scan);
+    }
+
+    /// Deletes or links the marked files in the background.
+    pub fn resolve(&mut self, action: DuplicateAction) {
+        if self.progress.is_some() || self.marked.is_empty() {
+            return;
+        }
+        self.progress = Some(DuplicatesProgress {
+            action,
+            done: 0,
+            total: self.marked.len(),
+        });
+        self.request = Some(DuplicatesJob::Resolve(action));
+    }
+
+    pub fn toggle_both(&mut self) {
+        self.both = !self.both;
+        self.rescan();
+    }
+
+    pub fn on_scanned(&mut self, id: u64, result: io::Result<Vec<DuplicateGroup>>) {
+        if id != self.id || self.request.is_some() {
+            return;
+        }
+
+        match result {
+            Ok(v) => {
+                self.groups = Some(v);
+                self.select(0);
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => error!("Failed to find duplicates in {:?}: {}", self.get_roots(), e),
+        }
+    }
+
+    pub fn on_resolved(&mut self, id: u64, path: &Path, result: io::Result<()>) {
+        let Some(progress) = self.progress.as_mut().filter(|_| id == self.id) else {
+            return;
+        };
+        progress.done += 1;
+        match result {
+            Ok(()) => self.remove(path),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => error!("Failed to {} {:?}: {}", progress.action.label(), path, e),
+        }
+    }
+
+    pub fn on_finished(&mut self, id: u64) {
+        if id == self.id {
+            self.progress = None;
+        }
+    }
+
+    /// Group and file index of every row, `None` for group headers.
+    pub fn get_rows(&self) -> Vec<(usize, Option<usize>)> {
+        self.groups
+            .iter()
+            .flatten()
+            .enumerate()
+            .flat_map(|(group, v)| {
+                std::iter::once((group, None))
+                    .chain((0..v.files.len()).map(move |v| (group, Some(v))))
+            })
+            .collect()
+    }
+
+    pub fn get_selected_path(&self) -> Option<&Path> {
+        let (group, file) = *self.get_rows().get(self.list_state.selected()?)?;
+        let groups = self.groups.as_ref()?;
+        Some(&groups[group].files[file?].path)
+    }
+
+    fn select(&mut self, index: usize) {
+        let index = match self.get_rows().len() {
+            0 => None,
+            len => Some(index.min(len - 1)),
+        };
+        self.list_state.select(index);
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let index = self.list_state.selected().unwrap_or_default();
+        self.select(index.saturating_add_signed(delta));
+    }
+
+    pub fn toggle_marked(&mut self) {
+        if let Some(path) = self.get_selected_path().map(Path::to_path_buf)
+            && !self.marked.remove(&path)
+        {
+            self.marked.insert(path);
+        }
+        self.move_by(1);
+    }
+
+    /// Marks every file of each group except the one `rule` keeps.
+    pub fn mark_by_rule(&mut self, rule: KeepRule) {
+        self.marked.clear();
+        for group in self.groups.iter().flatten() {
+            let kept = rule.pick(group, &self.left_root);
+            for (index, file) in group.files.iter().enumerate() {
+                if index != kept {
+                    self.marked.insert(file.path.clone());
+                }
+            }
+        }
+    }
+
+    /// Marked files along with an unmarked file of their group to link them to.
+    pub fn get_marked_with_original(&self) -> Vec<(PathBuf, Option<PathBuf>)> {
+        let mut result = Vec::new();
+        for group in self.groups.iter().flatten() {
+            let original = group
+                .files
+                .iter()
+                .find(|v| !self.marked.contains(&v.path))
+                .map(|v| v.path.clone());
+            for file in group.files.iter().filter(|v| self.marked.contains(&v.path)) {
+                result.push((file.path.clone(), original.clone()));
+            }
+        }
+        result
+    }
+
+    /// Drops a file that was deleted or linked, along with groups that no
+    /// longer have duplicates.
+    pub fn remove(&mut self, path: &Path) {
+        self.marked.remove(path);
+        if let Some(groups) = &mut self.groups {
+            for group in groups.iter_mut() {
+                group.files.retain(|v| v.path != path);
+            }
+            groups.retain(|v| v.files.len() > 1);
+        }
+        let index = self.list_state.selected().unwrap_or_default();
+        self.select(index);
+    }
+}
@@ -1,13 +1,32 @@
 use std::{
-    ffi::OsStr,
+    ffi::{OsStr, OsString},
+    fs::Metadata,
     path::{Path, PathBuf},
 };
 
 use crate::{
-    file_management::entry::Entry,
+    file_management::{entry::Entry, link::Link, ownership, xattrs},
     state::diode::{directory_state::DirectoryState, file_state::FileState},
 };
 
+/// Details of the selected entry that take lookups to read, such as user
+/// names from NSS. They are kept until the metadata of the entry changes or
+/// the entry is deselected.
+#[derive(Debug, Clone)]
+pub struct EntryDetails {
+    pub owner: Option<String>,
+    pub attributes: Vec<(OsString, Vec<u8>)>,
+}
+
+impl EntryDetails {
+    pub fn read(path: &Path, metadata: &Metadata) -> Self {
+        Self {
+            owner: ownership::format(metadata),
+            attributes: xattrs::list(path).unwrap_or_default(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum EntryState {
     Directory(DirectoryState),
@@ -29,6 +48,58 @@ impl EntryState {
         }
     }
 
+    pub fn metadata(&self) -> &Metadata {
+        match self {
+            EntryState::Directory(v) => &v.directory.metadata,
+            EntryState::File(v) => &v.file.metadata,
+        }
+    }
+
+    pub fn set_metadata(&mut self, metadata: Metadata) {
+        match self {
+            EntryState::Directory(v) => {
+                v.directory.metadata = metadata;
+                v.details = None;
+            }
+            EntryState::File(v) => {
+                v.file.metadata = metadata;
+                v.details = None;
+            }
+        }
+    }
+
+    pub fn details(&self) -> Option<&EntryDetails> {
+        match self {
+            EntryState::Directory(v) => v.details.as_ref(),
+            EntryState::File(v) => v.details.as_ref(),
+        }
+    }
+
+    /// Reads the details unless they are cached already.
+    pub fn update_details(&mut self) {
+        let (details, path, metadata) = match self {
+            EntryState::Directory(v) => (&mut v.details, &v.directory.path, &v.directory.metadata),
+            EntryState::File(v) => (&mut v.details, &v.file.path, &v.file.metadata),
+        };
+        if details.is_none() {
+            *details = Some(EntryDetails::read(path, metadata));
+        }
+    }
+
+    pub fn clear_details(&mut self) {
+        match self {
+            EntryState::Directory(v) => v.details = None,
+            EntryState::File(v) => v.details = None,
+        }
+    }
+
+    pub fn link(&self) -> Option<&Link> {
+        match self {
+            EntryState::Directory(v) => v.directory.link.as_ref(),
+            EntryState::File(v) => v.file.link.as_ref(),
+        }
+    }
+
     pub fn set_path(&mut self, path: PathBuf) {
         match self {
             EntryState::Directory(v) => v.directory.path = path,
@@ -57,6 +128,32 @@ impl EntryState {
         }
     }
 
+    /// Takes over the on-disk data of `other` while keeping the view state.
+    pub fn update_from(&mut self, other: EntryState) {
+        match (self, other) {
+            (EntryState::Directory(v), EntryState::Directory(other)) => {
+                v.directory = other.directory;
+                v.details = None;
+            }
+            (EntryState::File(v), EntryState::File(other)) => {
+                v.file = other.file;
+                v.details = None;
+            }
+            (this, mut other) => {
+                other.set_marked(this.is_marked());
+                other.set_selected(this.is_selected());
+                *this = other;
+            }
+        }
+    }
+
+    pub fn is_selected(&self) -> bool {
+        match self {
+            EntryState::Directory(v) => v.selected,
+            EntryState::File(v) => v.selected,
+        }
+    }
+
     pub fn is_marked(&self) -> bool {
         match self {
             EntryState::Directory(v) => v.marked,
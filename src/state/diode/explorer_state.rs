@@ -1,5 +1,6 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
+    fs,
     path::{Path, PathBuf},
 };
 
@@ -7,10 +8,20 @@ use futures::io;
 use log::error;
 
 use crate::{
-    file_management::entry,
+    file_management::{
+        directory::Directory,
+        entry::{self, Entry, LinkKind},
+        pattern::Pattern,
+    },
     state::diode::{
-        directory_state::DirectoryState, entry_state::EntryState,
-        selected::directory::SelectedDirectory, selected_entry::SelectedEntry,
+        analyzer_state::AnalyzerState,
+        directory_state::DirectoryState,
+        entry_state::EntryState,
+        history_state::{HistoryEntry, HistoryState},
+        loading_state::LoadingState,
+        selected::directory::SelectedDirectory,
+        selected_entry::SelectedEntry,
+        visible_state::{self, VisibleState},
     },
     ui::explorer::explorer_pane::ExplorerPaneState,
 };
@@ -44,24 +55,41 @@ macro_rules! get_entry_mut {
 pub(crate) use get_entry;
 pub(crate) use get_entry_mut;
 
+const SCROLL_OFF: usize = 3;
+
 #[derive(Debug)]
 pub struct ExplorerState {
     pub root: DirectoryState,
     pub entries: BTreeMap<PathBuf, EntryState>,
     pub selected: Option<PathBuf>,
     pub pane_state: ExplorerPaneState,
+    pub visual_anchor: Option<PathBuf>,
+    pub history: HistoryState,
+    pub loading: LoadingState,
+    pub follow_links: bool,
+    pub analyzer: Option<AnalyzerState>,
+    visible: VisibleState,
+    details_of: Option<PathBuf>,
 }
 
 impl ExplorerState {
-    pub fn try_new(root: DirectoryState) -> io::Result<Self> {
-        let entries = Self::get_entries(&root)?;
+    pub fn new(root: DirectoryState) -> Self {
+        let mut loading = LoadingState::default();
+        loading.request(root.directory.path.clone());
 
-        Ok(Self {
+        Self {
             root,
-            entries,
+            entries: BTreeMap::new(),
             selected: None,
             pane_state: ExplorerPaneState::new(),
-        })
+            visual_anchor: None,
+            history: HistoryState::default(),
+            loading,
+            follow_links: false,
+            analyzer: None,
+            visible: VisibleState::default(),
+            details_of: None,
+        }
     }
 
     pub fn with_selected(&mut self) -> Option<SelectedEntry<'_>> {
@@ -73,14 +101,6 @@ impl ExplorerState {
         }
     }
 
-    pub fn get_entries(root: &DirectoryState) -> io::Result<BTreeMap<PathBuf, EntryState>> {
-        Ok(root
-            .load_entry_states()?
-            .into_iter()
-            .map(|v| (v.path().to_owned(), v))
-            .collect())
-    }
-
     pub fn get_selected_entry(&self) -> Option<&EntryState> {
         if let Some(selected) = &self.selected {
             self.entries.get(selected)
@@ -89,6 +109,17 @@ impl ExplorerState {
         }
     }
 
+    pub fn toggle_analyzer(&mut self) {
+        self.analyzer = match self.analyzer {
+            Some(_) => None,
+            None => Some(AnalyzerState::new(self.root.directory.path.clone())),
+        };
+    }
+
+    pub fn toggle_follow_links(&mut self) {
+        self.follow_links = !self.follow_links;
+    }
+
     pub fn clear_marked(&mut self) {
         self.entries
             .iter_mut()
@@ -103,10 +134,117 @@ impl ExplorerState {
             (selected.path().to_owned(), selected.is_marked())
         };
 
-        self.entries
-            .iter_mut()
-            .filter(|(k, _)| k.starts_with(&path))
-            .for_each(|(_, v)| v.set_marked(!is_marked));
+        self.set_marked_subtree(&path, !is_marked, true);
+    }
+
+    pub fn set_marked(&mut self, path: &Path, value: bool) {
+        self.set_marked_subtree(path, value, true);
+    }
+
+    fn set_marked_subtree(&mut self, path: &Path, value: bool, descendants: bool) {
+        if let Some(entry) = self.entries.get_mut(path) {
+            entry.set_marked(value);
+        }
+        if descendants {
+            self.entries
+                .range_mut::<PathBuf, _>(visible_state::subtree_range(path))
+                .for_each(|(_, v)| v.set_marked(value));
+        }
+    }
+
+    pub fn count_matching(&self, pattern: &Pattern) -> usize {
+        self.get_visible_entries()
+            .filter(|(k, v)| self.matches(pattern, k, v))
+            .count()
+    }
+
+    pub fn set_marked_matching(&mut self, pattern: &Pattern, value: bool) {
+        self.set_marked_visible(|state, k, v| state.matches(pattern, k, v), |_| value);
+    }
+
+    pub fn invert_marked(&mut self) {
+        self.set_marked_visible(|_, _, _| true, |v| !v.is_marked());
+    }
+
+    pub fn mark_current_directory(&mut self) {
+        let directory = self
+            .selected
+            .as_ref()
+            .and_then(|v| v.parent())
+            .unwrap_or(&self.root.directory.path)
+            .to_owned();
+
+        self.set_marked_visible(|_, k, _| k.parent() == Some(&directory), |_| true);
+    }
+
+    pub fn mark_files(&mut self) {
+        self.set_marked_visible(|_, _, v| matches!(v, EntryState::File(_)), |_| true);
+    }
+
+    pub fn mark_directories(&mut self) {
+        self.set_marked_visible(|_, _, v| matches!(v, EntryState::Directory(_)), |_| true);
+    }
+
+    pub fn start_visual(&mut self) {
+        self.visual_anchor = self.selected.clone();
+    }
+
+    pub fn cancel_visual(&mut self) {
+        self.visual_anchor = None;
+    }
+
+    pub fn get_visual_range(&self) -> BTreeSet<&Path> {
+        let (Some(anchor), Some(selected)) = (&self.visual_anchor, &self.selected) else {
+            return BTreeSet::new();
+        };
+
+        let (Some(start), Some(end)) = (
+            self.visible.position(anchor),
+            self.visible.position(selected),
+        ) else {
+            return BTreeSet::new();
+        };
+
+        self.visible.paths()[start.min(end)..=start.max(end)]
+            .iter()
+            .map(PathBuf::as_path)
+            .collect()
+    }
+
+    pub fn apply_visual(&mut self) {
+        let range: BTreeSet<PathBuf> = self
+            .get_visual_range()
+            .into_iter()
+            .map(Path::to_path_buf)
+            .collect();
+        self.visual_anchor = None;
+        self.set_marked_visible(|_, k, _| range.contains(k), |_| true);
+    }
+
+    fn matches(&self, pattern: &Pattern, path: &Path, entry: &EntryState) -> bool {
+        let relative = path.strip_prefix(&self.root.directory.path).unwrap_or(path);
+        pattern.is_match(entry.name(), relative)
+    }
+
+    /// Applies `value` to every visible entry accepted by `filter`. Collapsed
+    /// directories carry their hidden descendants along so moves stay consistent.
+    fn set_marked_visible(
+        &mut self,
+        filter: impl Fn(&Self, &PathBuf, &EntryState) -> bool,
+        value: impl Fn(&EntryState) -> bool,
+    ) {
+        let changes: Vec<(PathBuf, bool, bool)> = self
+            .get_visible_entries()
+            .filter(|(k, v)| filter(self, k, v))
+            .map(|(k, v)| {
+                let collapsed = matches!(v, EntryState::Directory(d) if d.collapsed);
+                (k.clone(), value(v), collapsed)
+            })
+            .collect();
+
+        for (path, value, collapsed) in changes {
+            self.set_marked_subtree(&path, value, collapsed);
+        }
     }
 
     pub fn move_marked(
@@ -164,7 +302,37 @@ impl ExplorerState {
         Ok((old_entries, moved_entries))
     }
 
+    /// The marked entries, or the selected one when nothing is marked.
+    pub fn get_marked_or_selected(&self) -> Vec<PathBuf> {
+        let marked: Vec<PathBuf> = self
+            .entries
+            .iter()
+            .filter(|(_, v)| v.is_marked())
+            .map(|(k, _)| k.clone())
+            .collect();
+        match marked.is_empty() {
+            true => self.selected.iter().cloned().collect(),
+            false => marked,
+        }
+    }
+
+    /// Links every marked entry, except those inside a marked directory, into
+    /// `destination`. Failures are logged and the remaining entries still linked.
+    pub fn link_marked(&self, destination: &Path, kind: LinkKind) {
+        let mut linked: Vec<&PathBuf> = Vec::new();
+        for (path, _) in self.entries.iter().filter(|(_, v)| v.is_marked()) {
+            if linked.iter().any(|v| path.starts_with(v)) {
+                continue;
+            }
+            if let Err(e) = entry::link_entry(path, destination, kind) {
+                error!("Failed to link {:?}: {}", path, e);
+            }
+            linked.push(path);
+        }
+    }
+
     pub fn reload(&mut self, old_entries: Vec<EntryState>, new_entries: Vec<EntryState>) {
+        self.visible.invalidate();
         for entry in old_entries {
             self.entries.remove(entry.path());
         }
@@ -174,16 +342,180 @@ impl ExplorerState {
         }
     }
 
-    pub fn load_dir(directory: &DirectoryState) -> io::Result<BTreeMap<PathBuf, EntryState>> {
-        let is_marked = directory.marked;
-        Ok(directory
-            .load_entry_states()?
-            .into_iter()
-            .map(|mut v| {
-                v.set_marked(is_marked);
-                (v.path().to_owned(), v)
+    /// Re-reads the metadata of an entry that was changed in place.
+    pub fn update_metadata(&mut self, path: &Path) {
+        let Some(entry) = self.entries.get_mut(path) else {
+            return;
+        };
+        match fs::metadata(path).or_else(|_| fs::symlink_metadata(path)) {
+            Ok(v) => entry.set_metadata(v),
+            Err(e) => error!("Failed to read metadata of {:?}: {}", path, e),
+        }
+    }
+
+    pub fn get_loaded_directories(&self) -> BTreeSet<PathBuf> {
+        self.entries
+            .iter()
+            .filter_map(|(k, v)| match v {
+                EntryState::Directory(d) if !d.collapsed => Some(k.clone()),
+                _ => None,
             })
-            .collect())
+            .chain([self.root.directory.path.clone()])
+            .collect()
+    }
+
+    pub fn refresh(&mut self) {
+        let directories = self.get_loaded_directories();
+        self.sync_directories(directories);
+    }
+
+    pub fn sync_directories(&mut self, directories: impl IntoIterator<Item = PathBuf>) {
+        let loaded = self.get_loaded_directories();
+        for directory in directories {
+            if loaded.contains(&directory) {
+                self.loading.request(directory);
+            }
+        }
+    }
+
+    pub fn request_load(&mut self, directory: PathBuf) {
+        self.loading.request(directory);
+    }
+
+    /// Drops loads of directories that were collapsed or left behind.
+    pub fn prune_loads(&mut self) {
+        let loaded = self.get_loaded_directories();
+        self.loading.retain(&loaded);
+    }
+
+    pub fn on_entries_loaded(&mut self, id: u64, directory: &Path, entries: Vec<Entry>) {
+        let entries: Vec<EntryState> = entries.into_iter().map(EntryState::from).collect();
+        if !self
+            .loading
+            .record_seen(directory, id, entries.iter().map(|v| v.path()))
+        {
+            return;
+        }
+
+        self.visible.invalidate();
+        let is_marked = match self.entries.get(directory) {
+            Some(entry) => entry.is_marked(),
+            None => self.root.marked,
+        };
+
+        for mut entry in entries {
+            let path = entry.path().to_owned();
+            match self.entries.get_mut(&path) {
+                Some(existing) => existing.update_from(entry),
+                None => {
+                    entry.set_marked(is_marked);
+                    if let EntryState::Directory(d) = &mut entry
+                        && self.loading.pending_expansions.remove(&path)
+                    {
+                        d.collapsed = false;
+                        self.loading.request(path.clone());
+                    }
+                    self.entries.insert(path, entry);
+                }
+            }
+        }
+
+        if let Some(path) = &self.loading.pending_selection
+            && self.entries.contains_key(path)
+        {
+            let path = self.loading.pending_selection.take();
+            self.navigate_to(path);
+        }
+    }
+
+    /// Completes a load by removing the children that were not seen on disk.
+    pub fn on_load_finished(&mut self, id: u64, directory: &Path, result: io::Result<()>) {
+        let Some(seen) = self.loading.finish(directory, id) else {
+            return;
+        };
+
+        if let Err(e) = result {
+            error!("Failed to load {:?}: {}", directory, e);
+            return;
+        }
+
+        let visible = self.visible.paths().to_vec();
+        let removed: Vec<PathBuf> = self
+            .entries
+            .range::<PathBuf, _>(visible_state::subtree_range(directory))
+            .map(|(k, _)| k)
+            .filter(|k| k.parent() == Some(directory) && !seen.contains(*k))
+            .cloned()
+            .collect();
+        for path in removed {
+            self.remove_subtree(&path);
+        }
+
+        self.update_visible();
+        self.reselect(&visible);
+        if self.selected.is_none() && directory == self.root.directory.path {
+            let first_key = self.get_visible_entries().next().map(|(k, _)| k.clone());
+            self.navigate_to(first_key);
+        }
+    }
+
+    fn remove_subtree(&mut self, path: &Path) {
+        let descendants: Vec<PathBuf> = self
+            .entries
+            .range::<PathBuf, _>(visible_state::subtree_range(path))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for k in descendants {
+            self.entries.remove(&k);
+        }
+        self.entries.remove(path);
+        self.visible.invalidate();
+    }
+
+    pub fn invalidate_visible(&mut self) {
+        self.visible.invalidate();
+    }
+
+    pub fn update_visible(&mut self) {
+        self.visible.update(&self.entries);
+    }
+
+    /// Reads the details shown for the selected entry once it changed, and
+    /// drops those of the previous one so they are read again when it returns.
+    pub fn update_details(&mut self) {
+        if self.details_of != self.selected {
+            let previous = std::mem::replace(&mut self.details_of, self.selected.clone());
+            if let Some(entry) = previous.and_then(|v| self.entries.get_mut(&v)) {
+                entry.clear_details();
+            }
+        }
+        if let Some(entry) = self.selected.as_ref().and_then(|v| self.entries.get_mut(v)) {
+            entry.update_details();
+        }
+    }
+
+    /// Moves the cursor to the closest surviving entry when the selected one
+    /// disappeared, preferring the entries that followed it.
+    fn reselect(&mut self, previous_visible: &[PathBuf]) {
+        let Some(selected) = &self.selected else {
+            return;
+        };
+        if self.entries.contains_key(selected) {
+            return;
+        }
+
+        let index = previous_visible
+            .iter()
+            .position(|v| v == selected)
+            .unwrap_or_default();
+        let (before, after) = previous_visible.split_at(index);
+        let next = after
+            .iter()
+            .chain(before.iter().rev())
+            .find(|v| self.visible.position(v).is_some())
+            .or_else(|| self.visible.paths().first())
+            .cloned();
+        self.navigate_to(next);
     }
 
     pub fn navigate_to(&mut self, new_path: Option<PathBuf>) {
@@ -203,63 +535,132 @@ impl ExplorerState {
     }
 
     pub fn move_down(&mut self) {
-        let next = {
-            let mut entries = self.get_visible_entries();
-            match &self.selected {
-                Some(selected) => {
-                    let mut iter = entries.skip_while(|(k, _)| *k != selected);
-                    match iter.next() {
-                        Some(_) => iter.next().map(|(k, _)| k.clone()),
-                        None => self.get_visible_entries().next().map(|(k, _)| k.clone()),
-                    }
-                }
-                None => entries.next().map(|(k, _)| k.clone()),
-            }
-        };
+        self.update_visible();
+        let paths = self.visible.paths();
+        let next = match self
+            .selected
+            .as_ref()
+            .and_then(|v| self.visible.position(v))
+        {
+            Some(i) => paths.get(i + 1),
+            None => paths.first(),
+        }
+        .cloned();
 
         if let Some(path) = next {
             self.navigate_to(Some(path));
-            self.pane_state.list_state.select_next();
         }
     }
 
     pub fn move_up(&mut self) {
-        let next = {
-            let mut entries = self.get_visible_entries().rev();
-            match &self.selected {
-                Some(selected) => {
-                    let mut iter = entries.skip_while(|(k, _)| *k != selected);
-                    match iter.next() {
-                        Some(_) => iter.next().map(|(k, _)| k.clone()),
-                        None => self
-                            .get_visible_entries()
-                            .next_back()
-                            .map(|(k, _)| k.clone()),
-                    }
-                }
-                None => entries.next().map(|(k, _)| k.clone()),
-            }
-        };
+        self.update_visible();
+        let paths = self.visible.paths();
+        let next = match self
+            .selected
+            .as_ref()
+            .and_then(|v| self.visible.position(v))
+        {
+            Some(i) => i.checked_sub(1).and_then(|i| paths.get(i)),
+            None => paths.last(),
+        }
+        .cloned();
 
         if let Some(path) = next {
             self.navigate_to(Some(path));
-            self.pane_state.list_state.select_next();
         }
     }
 
-    pub fn get_visible_entries(&self) -> impl DoubleEndedIterator<Item = (&PathBuf, &EntryState)> {
-        let collapsed: Vec<&PathBuf> = self
-            .entries
+    pub fn move_by(&mut self, delta: isize) {
+        self.update_visible();
+        let paths = self.visible.paths();
+        let Some(last) = paths.len().checked_sub(1) else {
+            return;
+        };
+
+        let current = self
+            .selected
+            .as_ref()
+            .and_then(|v| self.visible.position(v))
+            .unwrap_or_default();
+        let next = current.saturating_add_signed(delta).min(last);
+        self.navigate_to(Some(paths[next].clone()));
+    }
+
+    pub fn move_half_page_down(&mut self) {
+        self.move_by((self.pane_state.height / 2).max(1) as isize);
+    }
+
+    pub fn move_half_page_up(&mut self) {
+        self.move_by(-((self.pane_state.height / 2).max(1) as isize));
+    }
+
+    pub fn move_page_down(&mut self) {
+        self.move_by(self.pane_state.height.max(1) as isize);
+    }
+
+    pub fn move_page_up(&mut self) {
+        self.move_by(-(self.pane_state.height.max(1) as isize));
+    }
+
+    pub fn move_to_top(&mut self) {
+        self.move_by(isize::MIN);
+    }
+
+    pub fn move_to_bottom(&mut self) {
+        self.move_by(isize::MAX);
+    }
+
+    /// Derives the list index from the selected path and scrolls so that it
+    /// stays on the same screen row when possible, keeping `SCROLL_OFF` rows
+    /// of context above and below.
+    pub fn sync_scroll(&mut self, height: usize) {
+        let position = self
+            .selected
+            .as_ref()
+            .and_then(|v| self.visible.position(v));
+        let pane_state = &mut self.pane_state;
+        pane_state.height = height;
+        pane_state.list_state.select(position);
+        // Nothing fits in a collapsed pane, keep the scroll until it is resized.
+        if height == 0 {
+            return;
+        }
+
+        let mut offset = pane_state.list_state.offset();
+        if let Some(position) = position {
+            if pane_state.anchor == self.selected {
+                offset = position.saturating_sub(pane_state.anchor_row);
+            }
+
+            let margin = SCROLL_OFF.min(height.saturating_sub(1) / 2);
+            if position < offset + margin {
+                offset = position.saturating_sub(margin);
+            } else if position + margin >= offset + height {
+                offset = (position + margin + 1).saturating_sub(height);
+            }
+        }
+        offset = offset.min(self.visible.paths().len().saturating_sub(height));
+
+        *pane_state.list_state.offset_mut() = offset;
+        pane_state.anchor = self.selected.clone();
+        pane_state.anchor_row = position.map(|v| v - offset).unwrap_or_default();
+    }
+
+    pub fn get_visible_window(&self) -> impl Iterator<Item = (&PathBuf, &EntryState)> {
+        self.visible
+            .paths()
             .iter()
-            .filter_map(|(k, v)| match v {
-                EntryState::Directory(d) if d.collapsed => Some(k),
-                _ => None,
-            })
-            .collect();
+            .skip(self.pane_state.list_state.offset())
+            .take(self.pane_state.height)
+            .filter_map(|k| self.entries.get_key_value(k))
+    }
 
-        self.entries
+    /// Visible entries as of the last `update_visible`, which the app runs before every event.
+    pub fn get_visible_entries(&self) -> impl DoubleEndedIterator<Item = (&PathBuf, &EntryState)> {
+        self.visible
+            .paths()
             .iter()
-            .filter(move |(k, _)| !collapsed.iter().any(|c| k.starts_with(c) && k != c))
+            .filter_map(|k| self.entries.get_key_value(k))
     }
 
     pub fn set_parent_as_new_root(&mut self) {
@@ -271,19 +672,122 @@ impl ExplorerState {
             }
         };
 
-        self.root = parent.into();
+        self.record_history();
+        let previous = std::mem::replace(&mut self.root, parent.into());
+        self.loading.clear_pending();
+        self.request_load(self.root.directory.path.clone());
+
+        // Keep the directory we came from selected once it shows up.
+        let previous = previous.directory.path;
+        if self.entries.contains_key(&previous) {
+            self.navigate_to(Some(previous));
+        } else {
+            self.navigate_to(None);
+            self.loading.pending_selection = Some(previous);
+        }
+    }
+
+    pub fn jump_to(&mut self, path: PathBuf) -> io::Result<()> {
+        let root = DirectoryState::from(Directory::try_from(path)?);
+
+        self.record_history();
+        self.root = root;
+        self.entries
+            .retain(|k, _| k.starts_with(&self.root.directory.path));
+        self.visible.invalidate();
+        self.loading.clear_pending();
+        self.request_load(self.root.directory.path.clone());
 
-        let entries = match ExplorerState::get_entries(&self.root) {
-            Ok(v) => v,
-            Err(e) => {
-                error!("Failed load entries: {}", e);
-                return;
-            }
-        };
-        let old_entries = std::mem::take(&mut self.entries);
-        self.entries = entries;
-        self.entries.extend(old_entries);
         let first_key = self.entries.keys().next().cloned();
-        self.navigate_to(first_key)
+        self.navigate_to(first_key);
+        Ok(())
+    }
+
+    pub fn reveal(&mut self, path: PathBuf) -> io::Result<()> {
+        if path.is_dir() {
+            return self.jump_to(path);
+        }
+
+        let parent = path
+            .parent()
+            .ok_or_else(|| io::Error::other("No parent"))?
+            .to_owned();
+        self.jump_to(parent)?;
+        if self.entries.contains_key(&path) {
+            self.navigate_to(Some(path));
+        } else {
+            self.loading.pending_selection = Some(path);
+        }
+        Ok(())
+    }
+
+    pub fn record_history(&mut self) {
+        let entry = self.create_history_entry();
+        self.history.push(entry);
+    }
+
+    pub fn go_back(&mut self) {
+        let current = self.create_history_entry();
+        if let Some(entry) = self.history.go_back(current) {
+            self.restore_history_entry(entry);
+        }
+    }
+
+    pub fn go_forward(&mut self) {
+        let current = self.create_history_entry();
+        if let Some(entry) = self.history.go_forward(current) {
+            self.restore_history_entry(entry);
+        }
+    }
+
+    fn create_history_entry(&self) -> HistoryEntry {
+        HistoryEntry {
+            root: self.root.clone(),
+            expanded: self
+                .entries
+                .iter()
+                .filter_map(|(k, v)| match v {
+                    EntryState::Directory(d) if !d.collapsed => Some(k.clone()),
+                    _ => None,
+                })
+                .collect(),
+            selected: self.selected.clone(),
+        }
+    }
+
+    fn restore_history_entry(&mut self, entry: HistoryEntry) {
+        self.root = entry.root;
+        self.loading.clear_pending();
+        self.request_load(self.root.directory.path.clone());
+
+        self.entries.values_mut().for_each(|v| {
+            if let EntryState::Directory(d) = v {
+                d.collapsed = true;
+            }
+        });
+        self.visible.invalidate();
+
+        // Directories that are not loaded yet are expanded once their parent delivers them.
+        for path in entry.expanded {
+            match self.entries.get_mut(&path) {
+                Some(EntryState::Directory(directory)) => {
+                    directory.collapsed = false;
+                    self.loading.request(path);
+                }
+                _ => {
+                    self.loading.pending_expansions.insert(path);
+                }
+            }
+        }
+
+        match entry.selected {
+            Some(selected) if self.entries.contains_key(&selected) => {
+                self.navigate_to(Some(selected))
+            }
+            selected => {
+                self.navigate_to(None);
+                self.loading.pending_selection = selected;
+            }
+        }
     }
 }
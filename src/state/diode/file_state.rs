@@ -1,10 +1,11 @@
-use crate::file_management::file::File;
+use crate::{file_management::file::File, state::diode::entry_state::EntryDetails};
 
 #[derive(Debug, Clone)]
 pub struct FileState {
     pub file: File,
     pub selected: bool,
     pub marked: bool,
+    pub details: Option<EntryDetails>,
 }
 
 impl FileState {
@@ -13,6 +14,7 @@ impl FileState {
             file,
             selected: false,
             marked: false,
+            details: None,
         }
     }
 }
@@ -0,0 +1,151 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::error;
+
+use crate::file_management::{
+    data,
+    frecency::{self, FrecencyEntry},
+};
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+const MAX_TOTAL_RANK: f64 = 10_000.0;
+
+#[derive(Debug, Default)]
+pub struct FrecencyState {
+    entries: Vec<FrecencyEntry>,
+    last_roots: Vec<PathBuf>,
+    file: Option<PathBuf>,
+}
+
+impl FrecencyState {
+    pub fn load() -> Self {
+        let file = match data::data_file("frecency") {
+            Ok(v) => v,
+            Err(e) => {
+                error!("Directory history will not be persisted: {}", e);
+                return Self::default();
+            }
+        };
+
+        let entries = frecency::load(&file).unwrap_or_else(|e| {
+            error!("Failed to load directory history from {:?}: {}", file, e);
+            Vec::new()
+        });
+
+        let mut state = Self {
+            entries,
+            last_roots: Vec::new(),
+            file: Some(file),
+        };
+        state.prune();
+        state
+    }
+
+    /// Records a visit for every root that was not already shown on the previous call.
+    pub fn record_roots(&mut self, roots: &[&Path]) {
+        let visited: Vec<&Path> = roots
+            .iter()
+            .copied()
+            .filter(|v| !self.last_roots.iter().any(|last| last == v))
+            .collect();
+        for root in &visited {
+            self.visit(root);
+        }
+        if !visited.is_empty() {
+            self.save();
+        }
+        self.last_roots = roots.iter().map(|v| v.to_path_buf()).collect();
+    }
+
+    pub fn find(&self, query: &str) -> Option<&Path> {
+        let keywords: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let now = now();
+
+        self.entries
+            .iter()
+            .filter(|v| is_match(&v.path, &keywords))
+            .filter(|v| v.path.is_dir())
+            .max_by(|a, b| score(a, now).total_cmp(&score(b, now)))
+            .map(|v| v.path.as_path())
+    }
+
+    pub fn prune(&mut self) {
+        let count = self.entries.len();
+        self.entries.retain(|v| v.path.is_dir());
+        if self.entries.len() != count {
+            self.save();
+        }
+    }
+
+    fn visit(&mut self, path: &Path) {
+        let now = now();
+        match self.entries.iter_mut().find(|v| v.path == path) {
+            Some(entry) => {
+                entry.rank += 1.0;
+                entry.last_accessed = now;
+            }
+            None => self.entries.push(FrecencyEntry {
+                path: path.to_path_buf(),
+                rank: 1.0,
+                last_accessed: now,
+            }),
+        }
+
+        let total: f64 = self.entries.iter().map(|v| v.rank).sum();
+        if total > MAX_TOTAL_RANK {
+            self.entries.iter_mut().for_each(|v| v.rank *= 0.9);
+            self.entries.retain(|v| v.rank >= 1.0);
+        }
+    }
+
+    fn save(&self) {
+        let Some(file) = &self.file else {
+            return;
+        };
+
+        if let Err(e) = frecency::save(file, &self.entries) {
+            error!("Failed to save directory history to {:?}: {}", file, e);
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|v| v.as_secs())
+        .unwrap_or_default()
+}
+
+fn score(entry: &FrecencyEntry, now: u64) -> f64 {
+    let age = now.saturating_sub(entry.last_accessed);
+    let factor = match age {
+        v if v < HOUR => 4.0,
+        v if v < DAY => 2.0,
+        v if v < WEEK => 0.5,
+        _ => 0.25,
+    };
+    entry.rank * factor
+}
+
+/// Keywords must appear in order, and the last one must be part of the final component.
+fn is_match(path: &Path, keywords: &[String]) -> bool {
+    let haystack = path.to_string_lossy().to_lowercase();
+    let mut position = 0;
+    for keyword in keywords {
+        match haystack[position..].find(keyword.as_str()) {
+            Some(i) => position += i + keyword.len(),
+            None => return false,
+        }
+    }
+
+    match (keywords.last(), path.file_name()) {
+        (Some(last), Some(name)) => name.to_string_lossy().to_lowercase().contains(last),
+        (Some(_), None) => false,
+        (None, _) => true,
+    }
+}
@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use crate::state::diode::directory_state::DirectoryState;
+
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub root: DirectoryState,
+    pub expanded: Vec<PathBuf>,
+    pub selected: Option<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+pub struct HistoryState {
+    back: Vec<HistoryEntry>,
+    forward: Vec<HistoryEntry>,
+}
+
+impl HistoryState {
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.back.push(entry);
+        self.forward.clear();
+    }
+
+    pub fn go_back(&mut self, current: HistoryEntry) -> Option<HistoryEntry> {
+        let entry = self.back.pop()?;
+        self.forward.push(current);
+        Some(entry)
+    }
+
+    pub fn go_forward(&mut self, current: HistoryEntry) -> Option<HistoryEntry> {
+        let entry = self.forward.pop()?;
+        self.back.push(current);
+        Some(entry)
+    }
+}
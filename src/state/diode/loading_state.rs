@@ -0,0 +1,85 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug)]
+pub struct LoadRequest {
+    pub id: u64,
+    pub directory: PathBuf,
+}
+
+#[derive(Debug)]
+struct ActiveLoad {
+    id: u64,
+    seen: BTreeSet<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+pub struct LoadingState {
+    next_id: u64,
+    active: BTreeMap<PathBuf, ActiveLoad>,
+    requests: Vec<LoadRequest>,
+    pub pending_selection: Option<PathBuf>,
+    pub pending_expansions: BTreeSet<PathBuf>,
+}
+
+impl LoadingState {
+    /// Starts a new load of `directory`, superseding any load still in flight.
+    pub fn request(&mut self, directory: PathBuf) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.active.insert(
+            directory.clone(),
+            ActiveLoad {
+                id,
+                seen: BTreeSet::new(),
+            },
+        );
+        self.requests.push(LoadRequest { id, directory });
+    }
+
+    pub fn take_requests(&mut self) -> Vec<LoadRequest> {
+        std::mem::take(&mut self.requests)
+    }
+
+    pub fn is_loading(&self, directory: &Path) -> bool {
+        self.active.contains_key(directory)
+    }
+
+    pub fn is_active(&self, id: u64) -> bool {
+        self.active.values().any(|v| v.id == id)
+    }
+
+    /// Returns false when the chunk belongs to a load that was superseded or dropped.
+    pub fn record_seen<'a>(
+        &mut self,
+        directory: &Path,
+        id: u64,
+        paths: impl IntoIterator<Item = &'a Path>,
+    ) -> bool {
+        match self.active.get_mut(directory) {
+            Some(load) if load.id == id => {
+                load.seen.extend(paths.into_iter().map(Path::to_path_buf));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn finish(&mut self, directory: &Path, id: u64) -> Option<BTreeSet<PathBuf>> {
+        match self.active.get(directory) {
+            Some(load) if load.id == id => self.active.remove(directory).map(|v| v.seen),
+            _ => None,
+        }
+    }
+
+    pub fn retain(&mut self, loaded: &BTreeSet<PathBuf>) {
+        self.active.retain(|k, _| loaded.contains(k));
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.pending_selection = None;
+        self.pending_expansions.clear();
+    }
+}
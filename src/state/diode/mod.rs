@@ -1,7 +1,22 @@
+pub mod analyzer_state;
+pub mod archive_state;
+pub mod attribute_state;
+pub mod bookmark_state;
+pub mod compare_state;
+pub mod diff_state;
 pub mod diode_state;
 pub mod directory_state;
+pub mod duplicates_state;
 pub mod entry_state;
 pub mod explorer_state;
 pub mod file_state;
+pub mod frecency_state;
+pub mod history_state;
+pub mod loading_state;
+pub mod permissions_state;
+pub mod prompt_state;
 pub mod selected;
 pub mod selected_entry;
+pub mod sync_state;
+pub mod usage_state;
+pub mod visible_state;
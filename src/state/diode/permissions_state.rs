@@ -0,0 +1,73 @@
+use std::{io, path::PathBuf};
+
+use crate::file_management::{permissions::ModeChange, walk::RecurseTarget};
+
+pub const ROWS: usize = 4;
+pub const COLUMNS: usize = 3;
+
+#[derive(Debug)]
+pub struct PermissionsState {
+    pub targets: Vec<PathBuf>,
+    pub mode: u32,
+    /// The mode the matrix started from, toggled bits are applied relative to it.
+    initial_mode: u32,
+    pub cursor: (usize, usize),
+    pub input: String,
+    pub recursion: Option<RecurseTarget>,
+}
+
+impl PermissionsState {
+    pub fn new(targets: Vec<PathBuf>, mode: u32) -> Self {
+        Self {
+            targets,
+            mode,
+            initial_mode: mode,
+            cursor: (0, 0),
+            input: String::new(),
+            recursion: None,
+        }
+    }
+
+    /// The mode bit behind a cell of the matrix. The first three rows are
+    /// user/group/other rwx, the last one is setuid/setgid/sticky.
+    pub fn get_bit(row: usize, column: usize) -> u32 {
+        match row {
+            3 => 1 << (11 - column),
+            _ => 1 << (8 - 3 * row - column),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        let (row, column) = self.cursor;
+        self.mode ^= Self::get_bit(row, column);
+    }
+
+    pub fn move_cursor(&mut self, rows: isize, columns: isize) {
+        let (row, column) = self.cursor;
+        self.cursor = (
+            row.saturating_add_signed(rows).min(ROWS - 1),
+            column.saturating_add_signed(columns).min(COLUMNS - 1),
+        );
+    }
+
+    pub fn cycle_recursion(&mut self) {
+        self.recursion = match self.recursion {
+            None => Some(RecurseTarget::All),
+            Some(RecurseTarget::All) => Some(RecurseTarget::Files),
+            Some(RecurseTarget::Files) => Some(RecurseTarget::Directories),
+            Some(RecurseTarget::Directories) => None,
+        };
+    }
+
+    /// Typed input takes precedence over the matrix. Only the bits toggled in
+    /// the matrix are changed, so each target keeps the rest of its own mode.
+    pub fn get_change(&self) -> io::Result<ModeChange> {
+        match self.input.trim().is_empty() {
+            true => Ok(ModeChange::Delta {
+                set: self.mode & !self.initial_mode,
+                clear: self.initial_mode & !self.mode,
+            }),
+            false => ModeChange::parse(&self.input),
+        }
+    }
+}
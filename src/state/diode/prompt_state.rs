@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use crate::file_management::{pattern::PatternSyntax, times::TouchSource};
+
+#[derive(Debug)]
+pub struct PromptState {
+    pub kind: PromptKind,
+    pub input: String,
+    pub completions: Vec<String>,
+    /// Best frecency match for the input, looked up when the input changes.
+    pub jump_target: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    Mark { marked: bool, syntax: PatternSyntax },
+    Bookmark,
+    GoTo,
+    Jump,
+    Ownership { recursive: bool },
+    Touch { source: TouchSource },
+    Xattr { remove: bool },
+    Delete { count: usize },
+    Archive,
+}
+
+impl PromptState {
+    pub fn new(kind: PromptKind) -> Self {
+        Self {
+            kind,
+            input: String::new(),
+            completions: Vec::new(),
+            jump_target: None,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self.kind {
+            PromptKind::Mark { marked, syntax } => {
+                let action = if marked { "Mark" } else { "Unmark" };
+                format!(" {} by {} (Tab: switch syntax) ", action, syntax.label())
+            }
+            PromptKind::Bookmark => " Bookmark name (single letter for a quick slot) ".to_owned(),
+            PromptKind::GoTo => " Go to path (Tab: complete) ".to_owned(),
+            PromptKind::Jump => " Jump to frequent directory ".to_owned(),
+            PromptKind::Ownership { recursive } => {
+                let recursive = if recursive { "on" } else { "off" };
+                format!(" Owner as user:group (Tab: recursive, {}) ", recursive)
+            }
+            PromptKind::Xattr { remove: false } => " Add attribute as name=value ".to_owned(),
+            PromptKind::Xattr { remove: true } => " Remove attribute ".to_owned(),
+            PromptKind::Delete { count } => {
+                format!(" Delete {} marked items? Type y to confirm ", count)
+            }
+            PromptKind::Touch { source } => {
+                format!(" Set times to {} (Tab: switch source) ", source.label())
+            }
+            PromptKind::Archive => {
+                " Archive name in the other pane (.tar, .tar.gz, .tar.zst or .zip) ".to_owned()
+            }
+        }
+    }
+}
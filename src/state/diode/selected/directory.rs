@@ -1,37 +1,54 @@
-use std::io;
+use log::{error, info};
 
-use crate::state::diode::{
-    entry_state::EntryState,
-    explorer_state::{ExplorerState, get_entry, get_entry_mut},
+use crate::{
+    file_management::link,
+    state::diode::{
+        entry_state::EntryState,
+        explorer_state::{ExplorerState, get_entry, get_entry_mut},
+    },
 };
 
-use log::error;
-
 pub struct SelectedDirectory<'a> {
     pub state: &'a mut ExplorerState,
 }
 
 impl SelectedDirectory<'_> {
-    pub fn toggle_dir(&mut self) -> io::Result<()> {
+    pub fn toggle_dir(&mut self) {
         let selected_path = self
             .state
             .selected
-            .as_ref()
+            .clone()
             .expect("SelectedDirectory guarantees selection exists");
 
-        let directory_state = get_entry_mut!(self.state, selected_path, Directory);
+        let follow_links = self.state.follow_links;
+        let directory_state = get_entry_mut!(self.state, &selected_path, Directory);
+
+        if directory_state.collapsed && directory_state.directory.link.is_some() {
+            if !follow_links {
+                info!("Not following link {:?}", selected_path);
+                return;
+            }
+            if link::is_loop(&selected_path) {
+                error!(
+                    "Not following {:?}: link points to an ancestor",
+                    selected_path
+                );
+                return;
+            }
+        }
 
         directory_state.collapsed = !directory_state.collapsed;
+        let collapsed = directory_state.collapsed;
+        self.state.invalidate_visible();
 
-        if !directory_state.collapsed {
-            for (k, v) in ExplorerState::load_dir(directory_state)? {
-                self.state.entries.entry(k).or_insert(v);
-            }
+        if !collapsed {
+            self.state.request_load(selected_path);
         }
-        Ok(())
     }
 
     pub fn set_dir_as_root(&mut self) {
+        self.state.record_history();
+
         let selected_path = self
             .state
             .selected
@@ -39,18 +56,9 @@ impl SelectedDirectory<'_> {
             .expect("SelectedDirectory guarantees selection exists");
 
         self.state.root = get_entry!(self.state, selected_path, Directory).clone();
-
-        let new_entries = match ExplorerState::get_entries(&self.state.root) {
-            Ok(v) => v,
-            Err(e) => {
-                error!("Failed load entries: {}", e);
-                return;
-            }
-        };
-
-        for (k, v) in new_entries {
-            self.state.entries.entry(k).or_insert(v);
-        }
+        self.state.loading.clear_pending();
+        self.state
+            .request_load(self.state.root.directory.path.to_owned());
 
         self.state
             .navigate_to(Some(self.state.root.directory.path.to_owned()))
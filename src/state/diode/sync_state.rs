@@ -0,0 +1,124 @@
+use std::{io, path::PathBuf};
+
+use log::error;
+use ratatui::widgets::ListState;
+
+use crate::file_management::sync::{SyncAction, SyncMode, SyncSummary};
+
+#[derive(Debug, PartialEq)]
+pub enum SyncStage {
+    Planning,
+    /// The plan is shown as a dry run until it is confirmed.
+    Ready,
+    Running {
+        done: usize,
+    },
+    Finished(SyncSummary),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncJob {
+    Plan,
+    Run,
+}
+
+/// Syncing the root of the other pane with the root of the current one.
+#[derive(Debug)]
+pub struct SyncState {
+    /// Identifies the running job, results of older jobs are dropped.
+    pub id: u64,
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    pub mode: SyncMode,
+    pub stage: SyncStage,
+    pub actions: Vec<SyncAction>,
+    pub list_state: ListState,
+    request: Option<SyncJob>,
+}
+
+impl SyncState {
+    pub fn new(source: PathBuf, destination: PathBuf) -> Self {
+        Self {
+            id: 0,
+            source,
+            destination,
+            mode: SyncMode::Mirror,
+            stage: SyncStage::Planning,
+            actions: Vec::new(),
+            list_state: ListState::default(),
+            request: Some(SyncJob::Plan),
+        }
+    }
+
+    pub fn take_request(&mut self) -> Option<SyncJob> {
+        self.request.take()
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.stage, SyncStage::Running { .. })
+    }
+
+    /// Changes the mode and plans again, unless the sync has already started.
+    pub fn cycle_mode(&mut self) {
+        if !matches!(self.stage, SyncStage::Planning | SyncStage::Ready) {
+            return;
+        }
+        self.mode = self.mode.next();
+        self.stage = SyncStage::Planning;
+        self.actions.clear();
+        self.request = Some(SyncJob::Plan);
+    }
+
+    pub fn run(&mut self) {
+        if self.stage == SyncStage::Ready && !self.actions.is_empty() {
+            self.stage = SyncStage::Running { done: 0 };
+            self.request = Some(SyncJob::Run);
+        }
+    }
+
+    pub fn on_planned(&mut self, id: u64, result: io::Result<Vec<SyncAction>>) {
+        if id != self.id || self.stage != SyncStage::Planning {
+            return;
+        }
+
+        match result {
+            Ok(actions) => {
+                self.actions = actions;
+                self.stage = SyncStage::Ready;
+                self.select(0);
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => {
+                error!("Failed to plan sync of {:?}: {}", self.source, e);
+                self.stage = SyncStage::Failed(e.to_string());
+            }
+        }
+    }
+
+    pub fn on_progress(&mut self, id: u64, done: usize) {
+        if id == self.id && self.is_running() {
+            self.stage = SyncStage::Running { done };
+            self.select(done);
+        }
+    }
+
+    pub fn on_finished(&mut self, id: u64, summary: SyncSummary) {
+        if id == self.id {
+            self.stage = SyncStage::Finished(summary);
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        let index = match self.actions.len() {
+            0 => None,
+            len => Some(index.min(len - 1)),
+        };
+        self.list_state.select(index);
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let index = self.list_state.selected().unwrap_or_default();
+        self.select(index.saturating_add_signed(delta));
+    }
+}
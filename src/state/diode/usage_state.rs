@@ -0,0 +1,106 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    io,
+    path::{Path, PathBuf},
+};
+
+use log::error;
+
+use crate::file_management::usage::Usage;
+
+/// Directory sizes shared by both panes, keyed by path.
+#[derive(Debug, Default)]
+pub struct UsageState {
+    cache: HashMap<PathBuf, Usage>,
+    requests: BTreeSet<PathBuf>,
+    running: BTreeSet<PathBuf>,
+    stale: BTreeSet<PathBuf>,
+    /// Directories that could not be scanned, only scanned again on request.
+    failed: BTreeSet<PathBuf>,
+    pub auto: bool,
+}
+
+impl UsageState {
+    pub fn get(&self, path: &Path) -> Option<&Usage> {
+        self.cache.get(path)
+    }
+
+    pub fn get_paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.cache.keys()
+    }
+
+    pub fn is_scanning(&self, path: &Path) -> bool {
+        self.requests.contains(path) || self.running.contains(path)
+    }
+
+    pub fn toggle_auto(&mut self) {
+        self.auto = !self.auto;
+    }
+
+    /// Queues a scan unless the size is known, being computed already or
+    /// could not be computed before.
+    pub fn request(&mut self, path: PathBuf) {
+        if !self.cache.contains_key(&path)
+            && !self.running.contains(&path)
+            && !self.failed.contains(&path)
+        {
+            self.requests.insert(path);
+        }
+    }
+
+    /// Drops the cached size so the next request scans again.
+    pub fn rescan(&mut self, path: PathBuf) {
+        self.cache.remove(&path);
+        self.failed.remove(&path);
+        if self.running.contains(&path) {
+            self.stale.insert(path);
+        } else {
+            self.requests.insert(path);
+        }
+    }
+
+    /// Hands out queued scans while fewer than `limit` are running.
+    pub fn take_requests(&mut self, limit: usize) -> Vec<PathBuf> {
+        let count = limit.saturating_sub(self.running.len());
+        let taken: Vec<PathBuf> = self.requests.iter().take(count).cloned().collect();
+        for path in &taken {
+            self.requests.remove(path);
+            self.running.insert(path.clone());
+        }
+        taken
+    }
+
+    pub fn finish(&mut self, path: PathBuf, result: io::Result<Usage>) {
+        self.running.remove(&path);
+        if self.stale.remove(&path) {
+            self.requests.insert(path);
+            return;
+        }
+
+        match result {
+            Ok(v) => {
+                self.cache.insert(path, v);
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => {
+                error!("Failed to compute size of {:?}: {}", path, e);
+                self.failed.insert(path);
+            }
+        }
+    }
+
+    /// A change inside `directory` changes the size of it and every ancestor,
+    /// so those are scanned again. Only loaded directories are watched, so
+    /// changes deeper down leave cached sizes outdated until they are rescanned.
+    pub fn invalidate(&mut self, directory: &Path) {
+        for path in directory.ancestors() {
+            self.failed.remove(path);
+            if self.cache.remove(path).is_some() {
+                self.requests.insert(path.to_path_buf());
+            }
+            if self.running.contains(path) {
+                self.stale.insert(path.to_path_buf());
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsString,
+    ops::Bound,
+    path::{Path, PathBuf},
+};
+
+use crate::state::diode::entry_state::EntryState;
+
+/// Flattened list of the visible entries, rebuilt only after it was invalidated.
+#[derive(Debug, Default)]
+pub struct VisibleState {
+    paths: Vec<PathBuf>,
+    positions: HashMap<PathBuf, usize>,
+    dirty: bool,
+}
+
+impl VisibleState {
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn update(&mut self, entries: &BTreeMap<PathBuf, EntryState>) {
+        if !self.dirty {
+            return;
+        }
+
+        self.paths.clear();
+        let mut iter = entries.range::<PathBuf, _>(..);
+        while let Some((k, v)) = iter.next() {
+            self.paths.push(k.clone());
+            if let EntryState::Directory(d) = v
+                && d.collapsed
+                && let Some(end) = subtree_end(k)
+            {
+                iter = entries.range::<PathBuf, _>((Bound::Included(end), Bound::Unbounded));
+            }
+        }
+
+        self.positions = self
+            .paths
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (k.clone(), i))
+            .collect();
+        self.dirty = false;
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    pub fn position(&self, path: &Path) -> Option<usize> {
+        self.positions.get(path).copied()
+    }
+}
+
+/// Returns the smallest path that sorts after every descendant of `path`.
+///
+/// Paths compare component-wise, so appending a NUL byte to the last
+/// component yields a bound that is greater than `path/...` but smaller than
+/// any following sibling.
+pub fn subtree_end(path: &Path) -> Option<PathBuf> {
+    let mut name = OsString::from(path.file_name()?);
+    name.push("\0");
+    Some(path.with_file_name(name))
+}
+
+pub fn subtree_range(path: &Path) -> (Bound<PathBuf>, Bound<PathBuf>) {
+    match subtree_end(path) {
+        Some(end) => (Bound::Excluded(path.to_path_buf()), Bound::Excluded(end)),
+        None => (Bound::Excluded(path.to_path_buf()), Bound::Unbounded),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn subtree_range_covers_only_descendants() {
+        let paths: BTreeSet<PathBuf> = [
+            "/a", "/a/b", "/a/b/c", "/a/b.txt", "/a/b-c", "/a/ba", "/a/c", "/b",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let subtree: Vec<&PathBuf> = paths
+            .range::<PathBuf, _>(subtree_range(Path::new("/a/b")))
+            .collect();
+        assert_eq!(subtree, [Path::new("/a/b/c")]);
+
+        let subtree: Vec<&PathBuf> = paths
+            .range::<PathBuf, _>(subtree_range(Path::new("/a")))
+            .collect();
+        assert_eq!(subtree.len(), 6);
+        assert!(subtree.iter().all(|v| v.starts_with("/a")));
+    }
+
+    #[test]
+    fn the_root_subtree_is_unbounded() {
+        assert_eq!(subtree_end(Path::new("/")), None);
+        assert_eq!(subtree_range(Path::new("/")).1, Bound::<PathBuf>::Unbounded);
+    }
+}
@@ -0,0 +1,83 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+use crate::{
+    file_management::usage::{self, UsageNode},
+    state::diode::analyzer_state::AnalyzerState,
+};
+
+const BAR_WIDTH: usize = 20;
+
+pub fn draw(frame: &mut Frame, area: Rect, analyzer: Option<&mut AnalyzerState>, selected: bool) {
+    let Some(analyzer) = analyzer else {
+        return;
+    };
+
+    let border_color = if selected {
+        Color::Cyan
+    } else {
+        Color::DarkGray
+    };
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title_bottom(" Enter: open  Backspace: up  Space: mark  d: delete  m: move ");
+
+    frame.render_widget(Clear, area);
+    let Some(current) = analyzer.get_current() else {
+        block = block.title(format!(" Analyzing {} ", analyzer.root.display()));
+        frame.render_widget(Paragraph::new("Scanning...").block(block), area);
+        return;
+    };
+
+    block = block.title(format!(
+        " {} - {} in {} files ",
+        analyzer.get_current_path().display(),
+        usage::format_size(current.usage.size),
+        current.usage.files
+    ));
+    let current_path = analyzer.get_current_path();
+    let items: Vec<ListItem> = current
+        .children
+        .iter()
+        .map(|v| {
+            let item = create_list_item(v, current.usage.size);
+            match analyzer.marked.contains(&current_path.join(&v.name)) {
+                true => item.style(
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                false => item,
+            }
+        })
+        .collect();
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    frame.render_stateful_widget(list, area, &mut analyzer.list_state);
+}
+
+fn create_list_item(node: &UsageNode, total: u64) -> ListItem<'static> {
+    let ratio = match total {
+        0 => 0.0,
+        _ => node.usage.size as f64 / total as f64,
+    };
+    let filled = (ratio * BAR_WIDTH as f64).round() as usize;
+    let suffix = if node.is_dir { "/" } else { "" };
+    ListItem::new(format!(
+        "{:>10} {}{} {:>5.1}% {}{}",
+        usage::format_size(node.usage.size),
+        "█".repeat(filled),
+        "░".repeat(BAR_WIDTH - filled),
+        ratio * 100.0,
+        node.name.to_string_lossy(),
+        suffix
+    ))
+}
@@ -3,9 +3,19 @@ use ratatui::{
     layout::{Constraint, Layout},
 };
 
+use crate::ui::analyzer;
+use crate::ui::bookmarks;
+use crate::ui::diff;
+use crate::ui::duplicates;
 use crate::ui::footer;
 use crate::ui::header;
-use crate::{state::diode::diode_state::DiodeState, ui::body};
+use crate::ui::permissions;
+use crate::ui::prompt;
+use crate::ui::sync;
+use crate::{
+    state::diode::diode_state::{DiodeState, Selection},
+    ui::body,
+};
 
 pub fn draw(frame: &mut Frame, diode_state: &mut DiodeState) {
     let chunks = Layout::vertical([
@@ -17,7 +27,12 @@ pub fn draw(frame: &mut Frame, diode_state: &mut DiodeState) {
 
     frame.render_widget(header::new("diode"), chunks[0]);
 
+    let list_height = body::list_height(chunks[1]);
+    diode_state.left_state.sync_scroll(list_height);
+    diode_state.right_state.sync_scroll(list_height);
+
     let [layout_left, layout_right] = body::new(chunks[1], diode_state);
+    let (rect_left, rect_right) = (layout_left.rect, layout_right.rect);
     frame.render_stateful_widget(
         layout_left.pane,
         layout_left.rect,
@@ -29,5 +44,25 @@ pub fn draw(frame: &mut Frame, diode_state: &mut DiodeState) {
         &mut diode_state.right_state.pane_state,
     );
 
-    frame.render_widget(footer::new(), chunks[2]);
+    analyzer::draw(
+        frame,
+        rect_left,
+        diode_state.left_state.analyzer.as_mut(),
+        diode_state.selected == Selection::Left,
+    );
+    analyzer::draw(
+        frame,
+        rect_right,
+        diode_state.right_state.analyzer.as_mut(),
+        diode_state.selected == Selection::Right,
+    );
+
+    bookmarks::draw(frame, chunks[1], &diode_state.bookmarks);
+    permissions::draw(frame, chunks[1], diode_state.permissions.as_ref());
+    sync::draw(frame, chunks[1], diode_state.sync.as_mut());
+    diff::draw(frame, chunks[1], diode_state.diff.as_mut());
+    duplicates::draw(frame, chunks[1], diode_state.duplicates.as_mut());
+    prompt::draw(frame, chunks[1], diode_state);
+
+    frame.render_widget(footer::new(&diode_state.archives), chunks[2]);
 }
@@ -17,6 +17,8 @@ pub fn new(area: Rect, diode_state: &DiodeState) -> [LayoutPanePair; 2] {
             rect: rects[0],
             pane: explorer_pane::create_pane(
                 &diode_state.left_state,
+                &diode_state.usage,
+                diode_state.compare.as_ref().map(|v| v.get(Selection::Left)),
                 diode_state.selected == Selection::Left,
                 &diode_state.left_state.root.directory.path,
             ),
@@ -25,6 +27,11 @@ pub fn new(area: Rect, diode_state: &DiodeState) -> [LayoutPanePair; 2] {
             rect: rects[1],
             pane: explorer_pane::create_pane(
                 &diode_state.right_state,
+                &diode_state.usage,
+                diode_state
+                    .compare
+                    .as_ref()
+                    .map(|v| v.get(Selection::Right)),
                 diode_state.selected == Selection::Right,
                 &diode_state.right_state.root.directory.path,
             ),
@@ -32,6 +39,11 @@ pub fn new(area: Rect, diode_state: &DiodeState) -> [LayoutPanePair; 2] {
     ]
 }
 
+pub fn list_height(area: Rect) -> usize {
+    // Each pane draws a border above and below its list.
+    area.height.saturating_sub(2) as usize
+}
+
 fn create_layout(area: Rect) -> [Rect; 2] {
     let rects = Layout::default()
         .direction(Direction::Horizontal)
@@ -0,0 +1,48 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+};
+
+use crate::state::diode::bookmark_state::BookmarkState;
+
+pub fn draw(frame: &mut Frame, area: Rect, bookmarks: &BookmarkState) {
+    let Some(index) = bookmarks.picker else {
+        return;
+    };
+
+    let area = create_area(area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Bookmarks ")
+        .title_bottom(" Enter: this pane  o: other pane  d: delete ");
+    let items: Vec<ListItem> = bookmarks
+        .bookmarks
+        .iter()
+        .map(|(name, path)| ListItem::new(format!("{:<12} {}", name, path.display())))
+        .collect();
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(
+        list,
+        area,
+        &mut ListState::default().with_selected(Some(index)),
+    );
+}
+
+fn create_area(area: Rect) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Percentage(60)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Percentage(60)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
@@ -0,0 +1,165 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    file_management::diff::{DiffLine, FileDiff, Hunk, LineKind},
+    state::diode::diff_state::DiffState,
+};
+
+pub fn draw(frame: &mut Frame, area: Rect, diff: Option<&mut DiffState>) {
+    let Some(diff) = diff else {
+        return;
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(
+            " {} ↔ {} ",
+            diff.left.display(),
+            diff.right.display()
+        ))
+        .title_bottom(" n/N: next/previous hunk  s: side by side  Esc: close ");
+    let inner = block.inner(area);
+    diff.height = inner.height as usize;
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let hunks = match &diff.diff {
+        None => {
+            frame.render_widget(Paragraph::new("Comparing..."), inner);
+            return;
+        }
+        Some(FileDiff::Text(hunks)) if hunks.is_empty() => {
+            frame.render_widget(Paragraph::new("Files are identical"), inner);
+            return;
+        }
+        Some(FileDiff::Text(hunks)) => hunks,
+        Some(FileDiff::Binary { differs }) => {
+            let text = match differs {
+                true => "Binary files differ",
+                false => "Binary files are identical",
+            };
+            frame.render_widget(Paragraph::new(text), inner);
+            return;
+        }
+    };
+
+    match diff.side_by_side {
+        false => {
+            let lines = create_window(
+                hunks,
+                &diff.hunk_rows,
+                diff.scroll,
+                diff.height,
+                |header| header,
+                |hunk| hunk.lines.iter().map(create_unified_line).collect(),
+            );
+            frame.render_widget(Paragraph::new(lines), inner);
+        }
+        true => {
+            let [left, right] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(inner);
+            let rows = create_window(
+                hunks,
+                &diff.hunk_rows,
+                diff.scroll,
+                diff.height,
+                |header| (header, Line::default()),
+                |hunk| {
+                    hunk.pair_lines()
+                        .into_iter()
+                        .map(|(left, right)| {
+                            (
+                                create_side_line(left, LineKind::Removed),
+                                create_side_line(right, LineKind::Added),
+                            )
+                        })
+                        .collect()
+                },
+            );
+            let (left_lines, right_lines): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+            frame.render_widget(Paragraph::new(left_lines), left);
+            frame.render_widget(Paragraph::new(right_lines), right);
+        }
+    }
+}
+
+/// Builds only the rows between `scroll` and `scroll + height`, starting at
+/// the last hunk whose header is at or above `scroll`.
+fn create_window<T>(
+    hunks: &[Hunk],
+    hunk_rows: &[usize],
+    scroll: usize,
+    height: usize,
+    create_header: impl Fn(Line<'static>) -> T,
+    create_rows: impl Fn(&Hunk) -> Vec<T>,
+) -> Vec<T> {
+    let first = hunk_rows
+        .partition_point(|v| *v <= scroll)
+        .saturating_sub(1);
+    let mut rows = Vec::with_capacity(height);
+    for (hunk, row) in hunks.iter().zip(hunk_rows).skip(first) {
+        if rows.len() >= height {
+            break;
+        }
+        let header = Line::styled(hunk.header(), Style::default().fg(Color::Cyan));
+        let all = std::iter::once(create_header(header)).chain(create_rows(hunk));
+        let remaining = height - rows.len();
+        rows.extend(all.skip(scroll.saturating_sub(*row)).take(remaining));
+    }
+    rows
+}
+
+fn create_unified_line(line: &DiffLine) -> Line<'static> {
+    let (sign, color) = match line.kind {
+        LineKind::Context => (" ", Color::Reset),
+        LineKind::Removed => ("-", Color::LightRed),
+        LineKind::Added => ("+", Color::LightGreen),
+    };
+    Line::from(vec![
+        create_number(line.left),
+        create_number(line.right),
+        Span::styled(
+            format!("{}{}", sign, expand_tabs(&line.text)),
+            Style::default().fg(color),
+        ),
+    ])
+}
+
+/// One side of a row, `side` being how a change shows up on this side.
+fn create_side_line(line: Option<&DiffLine>, side: LineKind) -> Line<'static> {
+    let Some(line) = line else {
+        return Line::default();
+    };
+    let number = match side {
+        LineKind::Removed => line.left,
+        _ => line.right,
+    };
+    let color = match (line.kind, side) {
+        (LineKind::Context, _) => Color::Reset,
+        (_, LineKind::Removed) => Color::LightRed,
+        _ => Color::LightGreen,
+    };
+    Line::from(vec![
+        create_number(number),
+        Span::styled(expand_tabs(&line.text), Style::default().fg(color)),
+    ])
+}
+
+fn create_number(number: Option<usize>) -> Span<'static> {
+    let text = number.map_or(String::new(), |v| (v + 1).to_string());
+    Span::styled(
+        format!("{:>5} ", text),
+        Style::default().fg(Color::DarkGray),
+    )
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', "    ")
+}
@@ -0,0 +1,86 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+use crate::{
+    file_management::{times, usage},
+    state::diode::duplicates_state::DuplicatesState,
+};
+
+pub fn draw(frame: &mut Frame, area: Rect, duplicates: Option<&mut DuplicatesState>) {
+    let Some(duplicates) = duplicates else {
+        return;
+    };
+
+    let roots: Vec<String> = duplicates
+        .get_roots()
+        .iter()
+        .map(|v| v.display().to_string())
+        .collect();
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title_bottom(
+            " Space: mark  o/s/l: keep oldest/shortest/left  d: delete  h: hard link  Tab: both panes ",
+        );
+
+    frame.render_widget(Clear, area);
+    let Some(groups) = &duplicates.groups else {
+        block = block.title(format!(" Duplicates in {} ", roots.join(", ")));
+        frame.render_widget(Paragraph::new("Scanning...").block(block), area);
+        return;
+    };
+
+    let wasted: u64 = groups.iter().map(|v| v.wasted()).sum();
+    let progress = match duplicates.progress {
+        Some(v) => format!(", {} {}/{}", v.action.label(), v.done, v.total),
+        None => String::new(),
+    };
+    block = block.title(format!(
+        " Duplicates in {} - {} groups, {} wasted, {} marked{} ",
+        roots.join(", "),
+        groups.len(),
+        usage::format_size(wasted),
+        duplicates.marked.len(),
+        progress
+    ));
+
+    let mut items = Vec::new();
+    for group in groups {
+        items.push(
+            ListItem::new(format!(
+                "{} copies of {}",
+                group.files.len(),
+                usage::format_size(group.size)
+            ))
+            .style(Style::default().fg(Color::Cyan)),
+        );
+        for file in &group.files {
+            let marked = duplicates.marked.contains(&file.path);
+            let item = ListItem::new(format!(
+                "  {} {}  {}",
+                if marked { "[x]" } else { "[ ]" },
+                times::format(file.modified),
+                file.path.display()
+            ));
+            items.push(match marked {
+                true => item.style(
+                    Style::default()
+                        .fg(Color::LightRed)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                false => item,
+            });
+        }
+    }
+
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    frame.render_stateful_widget(list, area, &mut duplicates.list_state);
+}
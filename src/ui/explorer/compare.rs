@@ -0,0 +1,30 @@
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::file_management::compare::Comparison;
+
+/// Colours an entry by its comparison. Marked entries keep the colour and are
+/// shown in bold, so auto-marked differences stay distinguishable.
+pub fn create_style(comparison: Option<Comparison>, marked: bool) -> Option<Style> {
+    let color = match comparison? {
+        Comparison::OnlyLeft => Color::LightBlue,
+        Comparison::OnlyRight => Color::LightMagenta,
+        Comparison::Newer => Color::LightGreen,
+        Comparison::Older => Color::LightRed,
+        Comparison::Identical => Color::DarkGray,
+    };
+    let style = Style::default().fg(color);
+    match marked {
+        true => Some(style.add_modifier(Modifier::BOLD)),
+        false => Some(style),
+    }
+}
+
+pub fn create_label(comparison: Comparison) -> &'static str {
+    match comparison {
+        Comparison::OnlyLeft => "only left",
+        Comparison::OnlyRight => "only right",
+        Comparison::Newer => "newer",
+        Comparison::Older => "older",
+        Comparison::Identical => "identical",
+    }
+}
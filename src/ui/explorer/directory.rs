@@ -1,24 +1,53 @@
-use crate::state::diode::directory_state::DirectoryState;
+use crate::{
+    file_management::{
+        compare::Comparison,
+        usage::{self, Usage},
+    },
+    state::diode::directory_state::DirectoryState,
+    ui::explorer::{compare, link},
+};
 use ratatui::{
     style::{Color, Modifier, Style},
     widgets::ListItem,
 };
 
-pub fn create_list_item(directory: &DirectoryState, indent: u8) -> Vec<ListItem<'static>> {
+pub fn create_list_item(
+    directory: &DirectoryState,
+    indent: u8,
+    in_visual: bool,
+    loading: bool,
+    usage: Option<&Usage>,
+    scanning: bool,
+    comparison: Option<Comparison>,
+) -> Vec<ListItem<'static>> {
     let mut items: Vec<ListItem> = Vec::new();
     let tabs = "  ".repeat(indent as usize);
-    let mut item = match directory.collapsed {
-        true => ListItem::new(format!(
-            "{}📁 {}",
-            tabs,
-            directory.directory.name.to_string_lossy()
-        )),
-        false => ListItem::new(format!(
-            "{}📂 {}",
-            tabs,
-            directory.directory.name.to_string_lossy()
-        )),
+    let icon = match directory.collapsed {
+        true => "📁",
+        false => "📂",
+    };
+    let suffix = match loading {
+        true => " ⏳",
+        false => "",
+    };
+    let size = match (usage, scanning) {
+        (_, true) => " (…)".to_owned(),
+        (Some(v), false) => format!(" ({}, {} files)", usage::format_size(v.size), v.files),
+        (None, false) => String::new(),
     };
+    let mut item = ListItem::new(format!(
+        "{}{} {}{}{}{}",
+        tabs,
+        icon,
+        directory.directory.name.to_string_lossy(),
+        link::create_suffix(directory.directory.link.as_ref()),
+        size,
+        suffix
+    ));
+
+    if let Some(style) = link::create_style(directory.directory.link.as_ref()) {
+        item = item.style(style);
+    }
 
     if directory.marked {
         item = item.style(
@@ -28,6 +57,14 @@ pub fn create_list_item(directory: &DirectoryState, indent: u8) -> Vec<ListItem<
         );
     }
 
+    if let Some(style) = compare::create_style(comparison, directory.marked) {
+        item = item.style(style);
+    }
+
+    if in_visual {
+        item = item.style(Style::default().bg(Color::DarkGray));
+    }
+
     if directory.selected {
         item = item.style(
             Style::default()
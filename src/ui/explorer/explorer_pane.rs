@@ -1,6 +1,10 @@
 use crate::{
-    state::diode::{entry_state::EntryState, explorer_state::ExplorerState},
-    ui::explorer::{directory, file},
+    file_management::{compare::Comparison, permissions, times, xattrs},
+    state::diode::{
+        entry_state::EntryState, explorer_state::ExplorerState, loading_state::LoadingState,
+        usage_state::UsageState,
+    },
+    ui::explorer::{compare, directory, file},
 };
 use ratatui::{
     buffer::Buffer,
@@ -8,7 +12,10 @@ use ratatui::{
     style::{Color, Style},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, StatefulWidget, Widget},
 };
-use std::path::Path;
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
 
 pub struct ExplorerPane {
     pub list: List<'static>,
@@ -19,29 +26,45 @@ pub struct ExplorerPane {
 #[derive(Debug)]
 pub struct ExplorerPaneState {
     pub list_state: ListState,
+    pub height: usize,
+    pub anchor: Option<PathBuf>,
+    pub anchor_row: usize,
 }
 
 impl ExplorerPaneState {
     pub fn new() -> Self {
         Self {
             list_state: ListState::default(),
+            height: 0,
+            anchor: None,
+            anchor_row: 0,
         }
     }
 }
 
 pub fn create_pane(
     explorer_state: &ExplorerState,
+    usage: &UsageState,
+    comparisons: Option<&HashMap<PathBuf, Comparison>>,
     selected: bool,
     base_path: &Path,
 ) -> ExplorerPane {
     let entries: Vec<&EntryState> = explorer_state
-        .get_visible_entries()
+        .get_visible_window()
         .map(|(_, v)| v)
         .collect();
+    let visual_range = explorer_state.get_visual_range();
 
     ExplorerPane {
-        list: create_list(&entries, base_path),
-        info: create_info(explorer_state),
+        list: create_list(
+            &entries,
+            &visual_range,
+            &explorer_state.loading,
+            usage,
+            comparisons,
+            base_path,
+        ),
+        info: create_info(explorer_state, comparisons),
         selected,
     }
 }
@@ -54,20 +77,91 @@ fn create_layout(area: Rect) -> [Rect; 2] {
     [rects[0], rects[1]]
 }
 
-fn create_list(entries: &[&EntryState], base_path: &Path) -> List<'static> {
+fn create_list(
+    entries: &[&EntryState],
+    visual_range: &BTreeSet<&Path>,
+    loading: &LoadingState,
+    usage: &UsageState,
+    comparisons: Option<&HashMap<PathBuf, Comparison>>,
+    base_path: &Path,
+) -> List<'static> {
     let items: Vec<ListItem> = entries
         .iter()
-        .flat_map(|v| match v {
-            EntryState::Directory(dir) => directory::create_list_item(dir, v.get_indent(base_path)),
-            EntryState::File(file) => vec![file::create_list_item(file, v.get_indent(base_path))],
+        .flat_map(|v| {
+            let in_visual = visual_range.contains(v.path());
+            let comparison = comparisons.and_then(|c| c.get(v.path())).copied();
+            match v {
+                EntryState::Directory(dir) => directory::create_list_item(
+                    dir,
+                    v.get_indent(base_path),
+                    in_visual,
+                    loading.is_loading(v.path()),
+                    usage.get(v.path()),
+                    usage.is_scanning(v.path()),
+                    comparison,
+                ),
+                EntryState::File(file) => vec![file::create_list_item(
+                    file,
+                    v.get_indent(base_path),
+                    in_visual,
+                    comparison,
+                )],
+            }
         })
         .collect();
     List::new(items)
 }
 
-fn create_info(explorer_state: &ExplorerState) -> Paragraph<'static> {
+fn create_info(
+    explorer_state: &ExplorerState,
+    comparisons: Option<&HashMap<PathBuf, Comparison>>,
+) -> Paragraph<'static> {
     if let Some(entry) = explorer_state.get_selected_entry() {
-        let text = format!("Title: {}", entry.name().to_string_lossy());
+        let mut text = format!("Title: {}", entry.name().to_string_lossy());
+        if let Some(link) = entry.link() {
+            let state = match entry {
+                _ if link.broken => " (broken)",
+                EntryState::Directory(_) if !explorer_state.follow_links => " (not followed)",
+                _ => "",
+            };
+            text.push_str(&format!("\nLink: {}{}", link.target.display(), state));
+        }
+        if let Some(comparison) = comparisons.and_then(|v| v.get(entry.path())) {
+            text.push_str(&format!(
+                "\nCompare: {}",
+                compare::create_label(*comparison)
+            ));
+        }
+        let mode = permissions::get_mode(entry.metadata());
+        text.push_str(&format!(
+            "\nMode: {} ({:04o})",
+            permissions::format(mode),
+            mode
+        ));
+        if let Some(owner) = entry.details().and_then(|v| v.owner.as_ref()) {
+            text.push_str(&format!("\nOwner: {}", owner));
+        }
+        if let Ok(modified) = entry.metadata().modified() {
+            text.push_str(&format!("\nModified: {}", times::format(modified)));
+        }
+        if let Some(details) = entry.details()
+            && !details.attributes.is_empty()
+        {
+            text.push_str("\nAttributes:");
+            for (name, value) in &details.attributes {
+                text.push_str(&format!(
+                    "\n  {} = {}",
+                    name.to_string_lossy(),
+                    xattrs::format_value(value)
+                ));
+            }
+        }
+        Paragraph::new(text)
+    } else if explorer_state
+        .loading
+        .is_loading(&explorer_state.root.directory.path)
+    {
+        let text = format!("Loading... {} items", explorer_state.entries.len());
         Paragraph::new(text)
     } else {
         let text = format!("{} items", explorer_state.entries.len());
@@ -90,7 +184,15 @@ impl StatefulWidget for ExplorerPane {
         let inner = block.inner(area);
         block.render(area, buf);
         let layout = create_layout(inner);
-        StatefulWidget::render(self.list, layout[0], buf, &mut state.list_state);
+        // Only the visible window was turned into items, so selection is relative to it.
+        let offset = state.list_state.offset();
+        let mut window_state = ListState::default().with_selected(
+            state
+                .list_state
+                .selected()
+                .and_then(|v| v.checked_sub(offset)),
+        );
+        StatefulWidget::render(self.list, layout[0], buf, &mut window_state);
         self.info.render(layout[1], buf);
     }
 }
@@ -1,12 +1,30 @@
-use crate::state::diode::file_state::FileState;
+use crate::{
+    file_management::compare::Comparison,
+    state::diode::file_state::FileState,
+    ui::explorer::{compare, link},
+};
 use ratatui::{
     style::{Color, Modifier, Style},
     widgets::ListItem,
 };
 
-pub fn create_list_item(file: &FileState, indent: u8) -> ListItem<'static> {
+pub fn create_list_item(
+    file: &FileState,
+    indent: u8,
+    in_visual: bool,
+    comparison: Option<Comparison>,
+) -> ListItem<'static> {
     let tabs = "  ".repeat(indent as usize);
-    let mut item = ListItem::new(format!("{}📄 {}", tabs, file.file.name.to_string_lossy()));
+    let mut item = ListItem::new(format!(
+        "{}📄 {}{}",
+        tabs,
+        file.file.name.to_string_lossy(),
+        link::create_suffix(file.file.link.as_ref())
+    ));
+
+    if let Some(style) = link::create_style(file.file.link.as_ref()) {
+        item = item.style(style);
+    }
 
     if file.marked {
         item = item.style(
@@ -16,6 +34,14 @@ pub fn create_list_item(file: &FileState, indent: u8) -> ListItem<'static> {
         );
     }
 
+    if let Some(style) = compare::create_style(comparison, file.marked) {
+        item = item.style(style);
+    }
+
+    if in_visual {
+        item = item.style(Style::default().bg(Color::DarkGray));
+    }
+
     if file.selected {
         item = item.style(
             Style::default()
@@ -0,0 +1,22 @@
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::file_management::link::Link;
+
+pub fn create_suffix(link: Option<&Link>) -> String {
+    match link {
+        Some(link) => format!(" -> {}", link.target.display()),
+        None => String::new(),
+    }
+}
+
+pub fn create_style(link: Option<&Link>) -> Option<Style> {
+    match link {
+        Some(link) if link.broken => Some(
+            Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::CROSSED_OUT),
+        ),
+        Some(_) => Some(Style::default().fg(Color::Cyan)),
+        None => None,
+    }
+}
@@ -1,3 +1,5 @@
+pub mod compare;
 pub mod directory;
 pub mod explorer_pane;
 pub mod file;
+pub mod link;
@@ -1,5 +1,21 @@
 use ratatui::widgets::{Block, Paragraph};
 
-pub fn new() -> Paragraph<'static> {
-    Paragraph::new("footer").block(Block::default()).centered()
+use crate::state::diode::archive_state::ArchiveState;
+
+pub fn new(archives: &ArchiveState) -> Paragraph<'static> {
+    if archives.jobs.is_empty() {
+        return Paragraph::new("footer").block(Block::default()).centered();
+    }
+
+    let jobs: Vec<String> = archives
+        .jobs
+        .iter()
+        .map(|v| {
+            let name = v.target.file_name().unwrap_or_default().to_string_lossy();
+            format!("Archiving {} {}/{}", name, v.done, v.total)
+        })
+        .collect();
+    Paragraph::new(jobs.join("  "))
+        .block(Block::default())
+        .centered()
 }
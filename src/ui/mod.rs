@@ -1,5 +1,12 @@
+mod analyzer;
 pub mod app;
 mod body;
+mod bookmarks;
+mod diff;
+mod duplicates;
 pub mod explorer;
 mod footer;
 mod header;
+mod permissions;
+mod prompt;
+mod sync;
@@ -0,0 +1,102 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    file_management::permissions,
+    state::diode::permissions_state::{COLUMNS, PermissionsState},
+};
+
+const ROW_LABELS: [&str; 4] = ["user", "group", "other", "special"];
+const HEADER: [&str; COLUMNS] = ["read", "write", "execute"];
+const SPECIAL_HEADER: [&str; COLUMNS] = ["setuid", "setgid", "sticky"];
+
+pub fn draw(frame: &mut Frame, area: Rect, permissions: Option<&PermissionsState>) {
+    let Some(permissions) = permissions else {
+        return;
+    };
+
+    let area = create_area(area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(
+            " Permissions ({} selected) ",
+            permissions.targets.len()
+        ))
+        .title_bottom(" Arrows: move  Space: toggle  Tab: recursion  Enter: apply ");
+
+    let mut lines = vec![create_header(&HEADER)];
+    for (row, label) in ROW_LABELS.iter().enumerate() {
+        if row == 3 {
+            lines.push(create_header(&SPECIAL_HEADER));
+        }
+        lines.push(create_row(permissions, row, label));
+    }
+    lines.push(Line::default());
+    lines.push(create_mode_line(permissions));
+    lines.push(Line::from(vec![
+        Span::raw("Input:     "),
+        Span::raw(permissions.input.clone()),
+        Span::styled("█", Style::default().fg(Color::Cyan)),
+    ]));
+    lines.push(Line::from(format!(
+        "Recursive: {}",
+        permissions.recursion.map_or("off", |v| v.label())
+    )));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn create_header(labels: &[&str; COLUMNS]) -> Line<'static> {
+    let mut text = format!("{:<10}", "");
+    for label in labels {
+        text.push_str(&format!("{:<9}", label));
+    }
+    Line::styled(text, Style::default().fg(Color::DarkGray))
+}
+
+fn create_row(permissions: &PermissionsState, row: usize, label: &str) -> Line<'static> {
+    let mut spans = vec![Span::raw(format!("{:<10}", label))];
+    for column in 0..COLUMNS {
+        let set = permissions.mode & PermissionsState::get_bit(row, column) != 0;
+        let style = match permissions.cursor == (row, column) {
+            true => Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::REVERSED),
+            false => Style::default(),
+        };
+        spans.push(Span::styled(if set { "[x]" } else { "[ ]" }, style));
+        spans.push(Span::raw(" ".repeat(6)));
+    }
+    Line::from(spans)
+}
+
+fn create_mode_line(permissions: &PermissionsState) -> Line<'static> {
+    match permissions.get_change() {
+        Ok(change) => {
+            let mode = change.apply(permissions.mode, false);
+            Line::from(format!(
+                "Mode:      {} ({:04o})",
+                permissions::format(mode),
+                mode
+            ))
+        }
+        Err(_) => Line::styled("Mode:      invalid input", Style::default().fg(Color::Red)),
+    }
+}
+
+fn create_area(area: Rect) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(60)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(13)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
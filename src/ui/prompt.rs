@@ -0,0 +1,148 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    file_management::{
+        archive::ArchiveFormat,
+        path,
+        pattern::Pattern,
+        times::{self, TouchSource},
+        xattrs,
+    },
+    state::diode::{
+        diode_state::DiodeState,
+        prompt_state::{PromptKind, PromptState},
+    },
+};
+
+pub fn draw(frame: &mut Frame, area: Rect, diode_state: &DiodeState) {
+    let Some(prompt) = &diode_state.prompt else {
+        return;
+    };
+
+    let area = create_area(area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(prompt.title())
+        .title_bottom(create_status(prompt, diode_state));
+    let input = Line::from(vec![
+        Span::raw(prompt.input.clone()),
+        Span::styled("█", Style::default().fg(Color::Cyan)),
+    ]);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(input).block(block), area);
+}
+
+fn create_area(area: Rect) -> Rect {
+    let height = 3.min(area.height);
+    Rect {
+        x: area.x,
+        y: area.y + area.height - height,
+        width: area.width,
+        height,
+    }
+}
+
+fn create_status(prompt: &PromptState, diode_state: &DiodeState) -> String {
+    match prompt.kind {
+        PromptKind::Mark { syntax, .. } => match Pattern::new(&prompt.input, syntax) {
+            Ok(pattern) => format!(
+                " {} matches ",
+                diode_state.get_current_state().count_matching(&pattern)
+            ),
+            Err(_) => " invalid pattern ".to_owned(),
+        },
+        PromptKind::GoTo if !prompt.completions.is_empty() => {
+            format!(" {} ", prompt.completions.join("  "))
+        }
+        PromptKind::GoTo => {
+            let base = &diode_state.get_current_state().root.directory.path;
+            format!(" {} ", path::expand(&prompt.input, base).display())
+        }
+        PromptKind::Jump => match &prompt.jump_target {
+            Some(v) => format!(" {} ", v.display()),
+            None => " no match ".to_owned(),
+        },
+        PromptKind::Ownership { .. } => {
+            let current_state = diode_state.get_current_state();
+            let owner = current_state
+                .get_marked_or_selected()
+                .first()
+                .and_then(|v| current_state.entries.get(v))
+                .and_then(|v| v.details())
+                .and_then(|v| v.owner.clone());
+            match owner {
+                Some(v) => format!(" current: {} ", v),
+                None => " current: unknown ".to_owned(),
+            }
+        }
+        PromptKind::Delete { .. } => " this cannot be undone ".to_owned(),
+        PromptKind::Archive => match ArchiveFormat::from_name(prompt.input.trim()) {
+            Some(_) => {
+                let count = diode_state
+                    .get_current_state()
+                    .get_marked_or_selected()
+                    .len();
+                let target = diode_state
+                    .get_other_state()
+                    .root
+                    .directory
+                    .path
+                    .join(prompt.input.trim());
+                format!(" {} entries into {} ", count, target.display())
+            }
+            None => " unknown format ".to_owned(),
+        },
+        PromptKind::Xattr { .. } => {
+            let name = prompt.input.split('=').next().unwrap_or_default().trim();
+            match name.is_empty() {
+                true => format!(" {}* ", xattrs::USER_NAMESPACE),
+                false => format!(" {} ", xattrs::to_user_name(name)),
+            }
+        }
+        PromptKind::Touch { source } => {
+            let current_state = diode_state.get_current_state();
+            match source {
+                TouchSource::Now => " now ".to_owned(),
+                TouchSource::Date => match times::parse(&prompt.input) {
+                    Ok(v) => format!(" {} ", times::format(v)),
+                    Err(_) => " invalid date ".to_owned(),
+                },
+                TouchSource::Other => {
+                    let other_state = diode_state.get_other_state();
+                    let counterpart = current_state
+                        .get_marked_or_selected()
+                        .first()
+                        .and_then(|v| {
+                            path::rebase(
+                                v,
+                                &current_state.root.directory.path,
+                                &other_state.root.directory.path,
+                            )
+                        })
+                        .filter(|v| v.exists());
+                    match counterpart {
+                        Some(v) => format!(" {} ", v.display()),
+                        None => " no matching entry ".to_owned(),
+                    }
+                }
+            }
+        }
+        PromptKind::Bookmark => format!(
+            " {} ",
+            diode_state
+                .get_current_state()
+                .root
+                .directory
+                .path
+                .display()
+        ),
+    }
+}
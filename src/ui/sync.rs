@@ -0,0 +1,121 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+use crate::{
+    file_management::sync::{SyncAction, SyncMode},
+    state::diode::sync_state::{SyncStage, SyncState},
+};
+
+pub fn draw(frame: &mut Frame, area: Rect, sync: Option<&mut SyncState>) {
+    let Some(sync) = sync else {
+        return;
+    };
+
+    let area = create_area(area);
+    let arrow = match sync.mode {
+        SyncMode::TwoWay => "⇄",
+        _ => "→",
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(
+            " Sync {} {} {} ",
+            sync.source.display(),
+            arrow,
+            sync.destination.display()
+        ))
+        .title_bottom(" Tab: mode  Enter: run  Esc: close ");
+    let inner = block.inner(area);
+    let [header, list] = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(inner);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(format!("Mode:   {}", sync.mode.label())),
+            create_status_line(sync),
+        ]),
+        header,
+    );
+
+    let done = match sync.stage {
+        SyncStage::Running { done } => done,
+        SyncStage::Finished(_) => sync.actions.len(),
+        _ => 0,
+    };
+    let items: Vec<ListItem> = sync
+        .actions
+        .iter()
+        .enumerate()
+        .map(|(index, action)| create_item(sync, action, index < done))
+        .collect();
+    let list_widget =
+        List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list_widget, list, &mut sync.list_state);
+}
+
+fn create_status_line(sync: &SyncState) -> Line<'static> {
+    match &sync.stage {
+        SyncStage::Planning => Line::from("Status: planning…"),
+        SyncStage::Ready if sync.actions.is_empty() => Line::from("Status: nothing to sync"),
+        SyncStage::Ready => Line::from(format!(
+            "Status: dry run, {} actions planned",
+            sync.actions.len()
+        )),
+        SyncStage::Running { done } => {
+            Line::from(format!("Status: running {}/{}", done, sync.actions.len()))
+        }
+        SyncStage::Finished(summary) => Line::styled(
+            format!(
+                "Status: done, {} copied, {} replaced, {} deleted, {} conflicts skipped, {} failed",
+                summary.copied, summary.replaced, summary.deleted, summary.skipped, summary.failed
+            ),
+            Style::default().fg(match summary.failed {
+                0 => Color::Green,
+                _ => Color::Red,
+            }),
+        ),
+        SyncStage::Failed(e) => {
+            Line::styled(format!("Status: {}", e), Style::default().fg(Color::Red))
+        }
+    }
+}
+
+/// Shows the target relative to its root, with the direction it is synced in.
+fn create_item(sync: &SyncState, action: &SyncAction, done: bool) -> ListItem<'static> {
+    let target = action.target();
+    let (arrow, relative) = match target.strip_prefix(&sync.destination) {
+        Ok(v) => ("→", v),
+        Err(_) => ("←", target.strip_prefix(&sync.source).unwrap_or(target)),
+    };
+    let color = match action {
+        _ if done => Color::DarkGray,
+        SyncAction::Copy { .. } => Color::LightGreen,
+        SyncAction::Replace { .. } => Color::LightYellow,
+        SyncAction::Delete(_) => Color::LightRed,
+        SyncAction::Conflict(_) => Color::LightMagenta,
+    };
+    ListItem::new(format!(
+        "{:<8} {} {}",
+        action.label(),
+        arrow,
+        relative.display()
+    ))
+    .style(Style::default().fg(color))
+}
+
+fn create_area(area: Rect) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Percentage(80)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Percentage(70)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}